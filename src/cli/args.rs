@@ -1,5 +1,6 @@
-use clap::{Parser, Subcommand};
-use crate::cli::Source;
+use clap::{ArgGroup, Parser, Subcommand, ValueEnum};
+use crate::cli::constants::STDIN_VALUE;
+use crate::cli::{Shell, Source};
 use std::io::{IsTerminal, Read, stdin};
 
 #[derive(Parser)]
@@ -10,11 +11,21 @@ use std::io::{IsTerminal, Read, stdin};
   - If both a pipe and a file are provided, the piped input takes priority.
   - If no file is provided, envmn assumes a `.env` file exists in the current directory (for convenience).
   - When a file path is provided (or .env is assumed), envmn reads from (and edits, if a file was passed) the file directly.
+  - `exec` never reads standard input, it is passed on to the command it runs.
+  - `set KEY -` reads the value from standard input instead, so it stays out of shell history.
 
 Examples:
   cat .env | envmn lint
   envmn format .env
-  envmn pick database_block .env > out.env
+  envmn pick database_block --file .env > out.env
+  envmn pick prod_database prod_cache prod_email --file .env
+  envmn switch prod
+  envmn use staging
+  envmn move staging_database --before local_overrides
+  envmn exec --pick prod_database -- cargo run
+  eval \"$(envmn export --shell bash)\"
+  envmn set DB_HOST db.example.com --block prod_database
+  envmn block copy prod_database staging_database
   envmn --version
 
 For more information, visit: https://github.com/devark28/envmn")]
@@ -33,6 +44,21 @@ pub enum ArgCommands {
     Lint {
         /// File to lint (defaults to .env)
         file: Option<String>,
+        /// Change the severity of a rule, e.g. `--rule lowercase-key=off`
+        #[arg(long = "rule", value_name = "RULE=off|info|warning|error")]
+        rules: Vec<String>,
+        /// Apply the fixes lint knows about and write the file back
+        #[arg(long)]
+        fix: bool,
+        /// With --fix, print a unified diff instead of writing
+        #[arg(long, requires = "fix")]
+        dry_run: bool,
+        /// Count variables set in the process environment as defined
+        #[arg(long)]
+        process_env: bool,
+        /// Print findings for people (text) or tools (json, sarif)
+        #[arg(long, value_enum, default_value_t = LintFormat::Text, conflicts_with = "fix")]
+        format: LintFormat,
     },
     /// Pretty-format the file
     Format {
@@ -43,23 +69,217 @@ pub enum ArgCommands {
     List {
         /// File to list blocks from (defaults to .env)
         file: Option<String>,
+        /// Print the blocks for people (text) or tools (json)
+        #[arg(long, value_enum, default_value_t = ListFormat::Text)]
+        format: ListFormat,
     },
-    /// Reorder the file by moving the specified block down
+    /// Reorder the file by moving the specified blocks down, in order
     Pick {
+        /// Block names to move, the last one ends up last
+        #[arg(required = true, value_name = "BLOCK")]
+        blocks: Vec<String>,
+        /// File to modify (defaults to .env)
+        #[arg(long)]
+        file: Option<String>,
+        /// Block that stays last, the picked block goes right above it
+        #[arg(long, value_name = "BLOCK")]
+        keep_last: Option<String>,
+    },
+    /// Pick every block of an environment, e.g. `prod_database` and `email_prod` for `prod`
+    Switch {
+        /// Environment to switch to
+        environment: String,
+        /// File to modify (defaults to .env)
+        file: Option<String>,
+    },
+    /// Pick the blocks of a profile from .envmn.toml, in order
+    Use {
+        /// Profile to apply
+        profile: String,
+        /// File to modify (defaults to .env)
+        file: Option<String>,
+    },
+    /// Uncomment the variables of a disabled block so they apply again
+    Enable {
+        /// Block to enable
+        block: String,
+        /// File to modify (defaults to .env)
+        file: Option<String>,
+    },
+    /// Comment out the variables of a block, for loaders where reordering does nothing
+    Disable {
+        /// Block to disable
+        block: String,
+        /// File to modify (defaults to .env)
+        file: Option<String>,
+    },
+    /// Show the active profile, group winners and where each variable comes from
+    Status {
+        /// File to look at (defaults to .env)
+        file: Option<String>,
+    },
+    /// Move a block to a precise place in the file
+    #[command(group(ArgGroup::new("position").required(true)))]
+    Move {
         /// Block name to move
         block: String,
         /// File to modify (defaults to .env)
         file: Option<String>,
+        /// Place it right above another block
+        #[arg(long, value_name = "BLOCK", group = "position")]
+        before: Option<String>,
+        /// Place it right below another block
+        #[arg(long, value_name = "BLOCK", group = "position")]
+        after: Option<String>,
+        /// Place it first, right after the variables outside blocks
+        #[arg(long, group = "position")]
+        top: bool,
+        /// Swap it with the block above
+        #[arg(long, group = "position")]
+        up: bool,
+        /// Swap it with the block below
+        #[arg(long, group = "position")]
+        down: bool,
+    },
+    /// Print the variables the file sets once later blocks override earlier ones
+    Resolve {
+        /// File to resolve (defaults to .env)
+        file: Option<String>,
+        /// Note the block each value comes from
+        #[arg(long)]
+        annotate: bool,
+        /// Expand ${VAR} references against the resolved variables
+        #[arg(long)]
+        expand: bool,
+    },
+    /// Show every block that sets a variable and which one wins
+    Explain {
+        /// Variable to explain
+        key: String,
+        /// File to look in (defaults to .env)
+        file: Option<String>,
+    },
+    /// Run a command with the variables the file resolves to
+    Exec {
+        /// File to load (defaults to .env)
+        file: Option<String>,
+        /// Move a block to the bottom first, without touching the file
+        #[arg(long = "pick", value_name = "BLOCK")]
+        picks: Vec<String>,
+        /// Expand ${VAR} references against the resolved variables
+        #[arg(long)]
+        expand: bool,
+        /// Command to run, after `--`
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
+    },
+    /// Print the resolved variables as commands for a shell to evaluate
+    Export {
+        /// File to export (defaults to .env)
+        file: Option<String>,
+        /// Shell to write the commands for
+        #[arg(long, value_enum, default_value_t = Shell::Posix)]
+        shell: Shell,
+        /// Expand ${VAR} references against the resolved variables
+        #[arg(long)]
+        expand: bool,
+    },
+    /// Print the value of a variable
+    Get {
+        /// Variable to print
+        key: String,
+        /// File to read (defaults to .env)
+        file: Option<String>,
+        /// Block to read from, instead of the value that wins
+        #[arg(long)]
+        block: Option<String>,
+    },
+    /// Set a variable in a block, adding it if needed
+    Set {
+        /// Variable to set
+        key: String,
+        /// New value, or `-` to read it from standard input
+        value: String,
+        /// File to modify (defaults to .env)
+        file: Option<String>,
+        /// Block to set it in (defaults to the default block)
+        #[arg(long)]
+        block: Option<String>,
+    },
+    /// Remove a variable from a block
+    Unset {
+        /// Variable to remove
+        key: String,
+        /// File to modify (defaults to .env)
+        file: Option<String>,
+        /// Block to remove it from (defaults to the default block)
+        #[arg(long)]
+        block: Option<String>,
+    },
+    /// Create, rename, copy or delete blocks
+    Block {
+        #[command(subcommand)]
+        action: BlockCommands,
     },
     /// Display the current version
     Version,
 }
 
+#[derive(Subcommand)]
+pub enum BlockCommands {
+    /// Add an empty block at the end of the file
+    New {
+        /// Name of the new block
+        name: String,
+        /// File to modify (defaults to .env)
+        file: Option<String>,
+    },
+    /// Rename a block where it stands
+    Rename {
+        /// Block to rename
+        from: String,
+        /// New name
+        to: String,
+        /// File to modify (defaults to .env)
+        file: Option<String>,
+    },
+    /// Copy a block, with its variables and comments, to the end of the file
+    Copy {
+        /// Block to copy
+        from: String,
+        /// Name of the copy
+        to: String,
+        /// File to modify (defaults to .env)
+        file: Option<String>,
+    },
+    /// Remove a block and its variables
+    Delete {
+        /// Block to remove
+        name: String,
+        /// File to modify (defaults to .env)
+        file: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum LintFormat {
+    Text,
+    Json,
+    Sarif,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ListFormat {
+    Text,
+    Json,
+}
+
 impl Args {
     pub fn parse_with_stdin() -> (Self, Option<Source>) {
+        let args = Self::parse();
         let stdin_input = {
             let mut buffer = String::new();
-            if !stdin().is_terminal() {
+            if !stdin().is_terminal() && !args.keeps_stdin() {
                 match stdin().read_to_string(&mut buffer) {
                     Ok(_) => Some(Source::StdIn(buffer)),
                     Err(_) => None,
@@ -68,6 +288,14 @@ impl Args {
                 None
             }
         };
-        (Self::parse(), stdin_input)
+        (args, stdin_input)
+    }
+    /// Whether standard input is left for something other than the file.
+    fn keeps_stdin(&self) -> bool {
+        match &self.command {
+            Some(ArgCommands::Exec { .. }) => true,
+            Some(ArgCommands::Set { value, .. }) => value == STDIN_VALUE,
+            _ => false,
+        }
     }
-}
\ No newline at end of file
+}
@@ -1,25 +1,113 @@
-use crate::cli::constants::DEFAULT_FILE;
+use crate::cli::constants::{DEFAULT_FILE, STDIN_VALUE};
 use crate::cli::{
-    Source,
-    args::{ArgCommands, Args},
+    Shell, Source,
+    args::{ArgCommands, Args, BlockCommands, LintFormat, ListFormat},
 };
-use crate::error::{CliErrors, Error};
+use crate::error::{AccessErrors, CliErrors, Error, Severity};
 use clap::CommandFactory;
+use std::io::{Read, stdin};
 use std::process::exit;
 
 #[derive(Clone, Debug)]
 pub struct Cli {
     pub input: Option<Source>,
     pub command: Commands,
+    pub output: OutputFormat,
+}
+
+/// How results and diagnostics are printed: rendered for people on stderr,
+/// or as a single JSON or SARIF document on stdout for tools.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Sarif,
 }
 
 #[derive(Clone, Debug)]
 pub enum Commands {
-    Version { name: String, version: String },
-    Lint,
+    Version {
+        name: String,
+        version: String,
+    },
+    Lint {
+        rules: Vec<(String, Option<Severity>)>,
+        fix: bool,
+        dry_run: bool,
+        process_env: bool,
+    },
     Format,
     List,
-    Pick { block_name: String },
+    Pick {
+        block_names: Vec<String>,
+        keep_last: Option<String>,
+    },
+    Switch {
+        environment: String,
+    },
+    Use {
+        profile: String,
+    },
+    Enable {
+        block_name: String,
+    },
+    Disable {
+        block_name: String,
+    },
+    Status,
+    Move {
+        block_name: String,
+        position: Position,
+    },
+    Resolve {
+        annotate: bool,
+        expand: bool,
+    },
+    Explain {
+        key: String,
+    },
+    Exec {
+        picks: Vec<String>,
+        command: Vec<String>,
+        expand: bool,
+    },
+    Export {
+        shell: Shell,
+        expand: bool,
+    },
+    Get {
+        key: String,
+        block_name: Option<String>,
+    },
+    Set {
+        key: String,
+        value: String,
+        block_name: Option<String>,
+    },
+    Unset {
+        key: String,
+        block_name: Option<String>,
+    },
+    Block {
+        action: BlockAction,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub enum Position {
+    Top,
+    Up,
+    Down,
+    Before(String),
+    After(String),
+}
+
+#[derive(Clone, Debug)]
+pub enum BlockAction {
+    New { name: String },
+    Rename { from: String, to: String },
+    Copy { from: String, to: String },
+    Delete { name: String },
 }
 
 impl Cli {
@@ -33,6 +121,7 @@ impl Cli {
                     name: env!("CARGO_PKG_NAME").to_string(),
                     version: env!("CARGO_PKG_VERSION").to_string(),
                 },
+                output: OutputFormat::Text,
             });
         }
 
@@ -43,21 +132,179 @@ impl Cli {
             };
         };
 
+        let mut output = OutputFormat::Text;
         let (command, input) = match command {
-            ArgCommands::Lint { file } => {
-                (Commands::Lint, Some(Self::resolve_input(file, stdin_input)))
+            ArgCommands::Lint {
+                file,
+                rules,
+                fix,
+                dry_run,
+                process_env,
+                format,
+            } => {
+                output = match format {
+                    LintFormat::Text => OutputFormat::Text,
+                    LintFormat::Json => OutputFormat::Json,
+                    LintFormat::Sarif => OutputFormat::Sarif,
+                };
+                (
+                    Commands::Lint {
+                        rules: rules
+                            .iter()
+                            .map(|rule| Self::parse_rule_level(rule))
+                            .collect::<Result<_, _>>()?,
+                        fix,
+                        dry_run,
+                        process_env,
+                    },
+                    Some(Self::resolve_input(file, stdin_input)),
+                )
             }
             ArgCommands::Format { file } => (
                 Commands::Format,
                 Some(Self::resolve_input(file, stdin_input)),
             ),
-            ArgCommands::List { file } => {
+            ArgCommands::List { file, format } => {
+                output = match format {
+                    ListFormat::Text => OutputFormat::Text,
+                    ListFormat::Json => OutputFormat::Json,
+                };
                 (Commands::List, Some(Self::resolve_input(file, stdin_input)))
             }
-            ArgCommands::Pick { block, file } => (
-                Commands::Pick { block_name: block },
+            ArgCommands::Pick {
+                blocks,
+                file,
+                keep_last,
+            } => (
+                Commands::Pick {
+                    block_names: blocks,
+                    keep_last,
+                },
+                Some(Self::resolve_input(file, stdin_input)),
+            ),
+            ArgCommands::Switch { environment, file } => (
+                Commands::Switch { environment },
+                Some(Self::resolve_input(file, stdin_input)),
+            ),
+            ArgCommands::Enable { block, file } => (
+                Commands::Enable { block_name: block },
+                Some(Self::resolve_input(file, stdin_input)),
+            ),
+            ArgCommands::Disable { block, file } => (
+                Commands::Disable { block_name: block },
+                Some(Self::resolve_input(file, stdin_input)),
+            ),
+            ArgCommands::Use { profile, file } => (
+                Commands::Use { profile },
+                Some(Self::resolve_input(file, stdin_input)),
+            ),
+            ArgCommands::Status { file } => (
+                Commands::Status,
+                Some(Self::resolve_input(file, stdin_input)),
+            ),
+            ArgCommands::Move {
+                block,
+                file,
+                before,
+                after,
+                top,
+                up,
+                down: _,
+            } => {
+                let position = match (before, after) {
+                    (Some(other), _) => Position::Before(other),
+                    (_, Some(other)) => Position::After(other),
+                    _ if top => Position::Top,
+                    _ if up => Position::Up,
+                    _ => Position::Down,
+                };
+                (
+                    Commands::Move {
+                        block_name: block,
+                        position,
+                    },
+                    Some(Self::resolve_input(file, stdin_input)),
+                )
+            }
+            ArgCommands::Resolve {
+                file,
+                annotate,
+                expand,
+            } => (
+                Commands::Resolve { annotate, expand },
                 Some(Self::resolve_input(file, stdin_input)),
             ),
+            ArgCommands::Explain { key, file } => (
+                Commands::Explain { key },
+                Some(Self::resolve_input(file, stdin_input)),
+            ),
+            ArgCommands::Exec {
+                file,
+                picks,
+                expand,
+                command,
+            } => (
+                Commands::Exec {
+                    picks,
+                    command,
+                    expand,
+                },
+                Some(Self::resolve_input(file, None)),
+            ),
+            ArgCommands::Export {
+                file,
+                shell,
+                expand,
+            } => (
+                Commands::Export { shell, expand },
+                Some(Self::resolve_input(file, stdin_input)),
+            ),
+            ArgCommands::Get { key, file, block } => (
+                Commands::Get {
+                    key,
+                    block_name: block,
+                },
+                Some(Self::resolve_input(file, stdin_input)),
+            ),
+            ArgCommands::Set {
+                key,
+                value,
+                file,
+                block,
+            } => (
+                Commands::Set {
+                    key,
+                    value: match value.as_str() {
+                        STDIN_VALUE => Self::read_value()?,
+                        _ => value,
+                    },
+                    block_name: block,
+                },
+                Some(Self::resolve_input(file, stdin_input)),
+            ),
+            ArgCommands::Unset { key, file, block } => (
+                Commands::Unset {
+                    key,
+                    block_name: block,
+                },
+                Some(Self::resolve_input(file, stdin_input)),
+            ),
+            ArgCommands::Block { action } => {
+                let (action, file) = match action {
+                    BlockCommands::New { name, file } => (BlockAction::New { name }, file),
+                    BlockCommands::Rename { from, to, file } => {
+                        (BlockAction::Rename { from, to }, file)
+                    }
+                    BlockCommands::Copy { from, to, file } => {
+                        (BlockAction::Copy { from, to }, file)
+                    }
+                    BlockCommands::Delete { name, file } => (BlockAction::Delete { name }, file),
+                };
+                (
+                    Commands::Block { action },
+                    Some(Self::resolve_input(file, stdin_input)),
+                )
+            }
             ArgCommands::Version => (
                 Commands::Version {
                     name: env!("CARGO_PKG_NAME").to_string(),
@@ -67,7 +314,39 @@ impl Cli {
             ),
         };
 
-        Ok(Cli { input, command })
+        Ok(Cli {
+            input,
+            command,
+            output,
+        })
+    }
+
+    fn parse_rule_level(arg: &str) -> Result<(String, Option<Severity>), Error> {
+        let invalid = || Error::CliError(CliErrors::InvalidRuleLevel(arg.to_string()));
+        let (rule, level) = arg.split_once('=').ok_or_else(invalid)?;
+        let severity = match level {
+            "off" => None,
+            "info" => Some(Severity::Info),
+            "warning" => Some(Severity::Warning),
+            "error" => Some(Severity::Error),
+            _ => return Err(invalid()),
+        };
+        Ok((rule.to_string(), severity))
+    }
+
+    /// A value piped in for `set`, without the line break `echo` adds.
+    fn read_value() -> Result<String, Error> {
+        let mut value = String::new();
+        stdin().read_to_string(&mut value).map_err(|error| {
+            Error::AccessError(AccessErrors::FileError(
+                "<stdin>".to_string(),
+                error.to_string(),
+            ))
+        })?;
+        let trimmed = value
+            .strip_suffix('\n')
+            .map(|value| value.strip_suffix('\r').unwrap_or(value));
+        Ok(trimmed.unwrap_or(&value).to_string())
     }
 
     fn resolve_input(file: Option<String>, stdin_input: Option<Source>) -> Source {
@@ -1,3 +1,4 @@
 pub const DEFAULT_FILE: &str = ".env";
+pub const STDIN_VALUE: &str = "-";
 /*pub const LONG_FLAG_PREFIX: &str = "--";
 pub const SHORT_FLAG_PREFIX: &str = "-";*/
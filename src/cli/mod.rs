@@ -1,8 +1,14 @@
+#[allow(clippy::module_inception)]
 mod cli;
 mod constants;
+mod shell;
 mod source;
 pub mod args;
 
+pub use cli::BlockAction;
 pub use cli::Cli;
 pub use cli::Commands;
+pub use cli::OutputFormat;
+pub use cli::Position;
+pub use shell::Shell;
 pub use source::Source;
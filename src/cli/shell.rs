@@ -0,0 +1,70 @@
+use clap::ValueEnum;
+
+/// The shells `export` can write for. Each one gets the syntax it evaluates
+/// without expanding anything inside the value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Shell {
+    Posix,
+    Bash,
+    Zsh,
+    Fish,
+    Nu,
+}
+
+impl Shell {
+    pub fn export(&self, key: &str, value: &str) -> String {
+        match self {
+            Shell::Posix | Shell::Bash | Shell::Zsh => {
+                format!("export {key}='{0}'", value.replace('\'', r"'\''"))
+            }
+            Shell::Fish => format!(
+                "set -gx {key} '{0}'",
+                value.replace('\\', r"\\").replace('\'', r"\'")
+            ),
+            Shell::Nu => {
+                let mut quoted = String::new();
+                for c in value.chars() {
+                    match c {
+                        '\\' => quoted.push_str(r"\\"),
+                        '"' => quoted.push_str("\\\""),
+                        '\n' => quoted.push_str(r"\n"),
+                        '\r' => quoted.push_str(r"\r"),
+                        '\t' => quoted.push_str(r"\t"),
+                        c => quoted.push(c),
+                    }
+                }
+                format!("$env.{key} = \"{quoted}\"")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn posix_single_quotes() {
+        assert_eq!(
+            Shell::Bash.export("A", "it's $HOME `x`"),
+            r"export A='it'\''s $HOME `x`'"
+        );
+        assert_eq!(Shell::Posix.export("A", "a\nb"), "export A='a\nb'");
+    }
+
+    #[test]
+    fn fish_escapes_backslash_and_quote() {
+        assert_eq!(
+            Shell::Fish.export("A", r"it's C:\dir $x"),
+            r"set -gx A 'it\'s C:\\dir $x'"
+        );
+    }
+
+    #[test]
+    fn nu_double_quotes_with_escapes() {
+        assert_eq!(
+            Shell::Nu.export("A", "say \"hi\"\n$x"),
+            r#"$env.A = "say \"hi\"\n$x""#
+        );
+    }
+}
@@ -1,5 +1,29 @@
+use crate::error::{AccessErrors, Error};
+use std::fs;
+
 #[derive(Clone, Debug)]
 pub enum Source {
     StdIn(String),
     FileName(String),
 }
+
+impl Source {
+    /// How the input is referred to in diagnostics.
+    pub fn name(&self) -> &str {
+        match self {
+            Source::StdIn(_) => "<stdin>",
+            Source::FileName(file_path) => file_path,
+        }
+    }
+    pub fn read(&self) -> Result<String, Error> {
+        match self {
+            Source::StdIn(content) => Ok(content.clone()),
+            Source::FileName(file_path) => fs::read_to_string(file_path).map_err(|error| {
+                Error::AccessError(AccessErrors::FileError(
+                    file_path.to_string(),
+                    error.to_string(),
+                ))
+            }),
+        }
+    }
+}
@@ -0,0 +1 @@
+pub const CONFIG_FILE: &str = ".envmn.toml";
@@ -0,0 +1,4 @@
+mod constants;
+mod profiles;
+
+pub use profiles::Profiles;
@@ -0,0 +1,124 @@
+use crate::cli::Source;
+use crate::config::constants::CONFIG_FILE;
+use crate::error::{AccessErrors, ConfigErrors, Error};
+use crate::parser::Span;
+use indexmap::IndexMap;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+/// Named block orders kept in the project's `.envmn.toml`. Each profile
+/// lists the blocks to pick, the last one ending up last:
+///
+/// ```toml
+/// staging = ["prod_database", "staging_cache", "dev_email"]
+/// ```
+#[derive(Debug, Default)]
+pub struct Profiles {
+    path: String,
+    profiles: IndexMap<String, Vec<String>>,
+}
+
+impl Profiles {
+    /// Reads the config next to the input file, or in the current directory
+    /// for piped input. A project without one has no profiles.
+    pub fn load(input: &Source) -> Result<Self, Error> {
+        let path = match input {
+            Source::FileName(file_path) => Path::new(file_path).with_file_name(CONFIG_FILE),
+            Source::StdIn(_) => PathBuf::from(CONFIG_FILE),
+        };
+        let path = path.display().to_string();
+        match fs::read_to_string(&path) {
+            Ok(text) => Self::parse(path, &text),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Profiles {
+                path,
+                ..Profiles::default()
+            }),
+            Err(error) => Err(Error::AccessError(AccessErrors::FileError(
+                path,
+                error.to_string(),
+            ))),
+        }
+    }
+    fn parse(path: String, text: &str) -> Result<Self, Error> {
+        let table = text.parse::<toml::Table>().map_err(|error| {
+            let span = error
+                .span()
+                .map(|range| Span::default().shifted(&text[..range.start]));
+            Error::ConfigError(ConfigErrors::InvalidConfig(
+                path.clone(),
+                span,
+                error.message().to_string(),
+            ))
+        })?;
+        let mut profiles = IndexMap::new();
+        for (name, value) in table {
+            let blocks = value
+                .as_array()
+                .and_then(|blocks| {
+                    blocks
+                        .iter()
+                        .map(|block| block.as_str().map(ToString::to_string))
+                        .collect::<Option<Vec<_>>>()
+                })
+                .ok_or_else(|| {
+                    Error::ConfigError(ConfigErrors::InvalidProfile(path.clone(), name.clone()))
+                })?;
+            profiles.insert(name, blocks);
+        }
+        Ok(Profiles { path, profiles })
+    }
+    pub fn get(&self, name: &str) -> Result<&[String], Error> {
+        self.profiles.get(name).map(Vec::as_slice).ok_or_else(|| {
+            Error::ConfigError(ConfigErrors::ProfileNotFound(
+                self.path.clone(),
+                name.to_string(),
+            ))
+        })
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.profiles
+            .iter()
+            .map(|(name, blocks)| (name.as_str(), blocks.as_slice()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profiles_keep_their_order() {
+        let profiles = Profiles::parse(
+            CONFIG_FILE.to_string(),
+            "staging = [\"prod_database\", \"staging_cache\"]\nlocal = []\n",
+        )
+        .unwrap();
+        assert_eq!(
+            profiles.iter().collect::<Vec<_>>(),
+            [
+                (
+                    "staging",
+                    &["prod_database".to_string(), "staging_cache".to_string()][..]
+                ),
+                ("local", &[][..]),
+            ]
+        );
+        assert!(profiles.get("missing").is_err());
+    }
+
+    #[test]
+    fn invalid_config() {
+        let error = Profiles::parse(CONFIG_FILE.to_string(), "a = [\"x\"]\nb = [").unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("Invalid config '.envmn.toml' at 2:6: ")
+        );
+
+        let error = Profiles::parse(CONFIG_FILE.to_string(), "a = \"x\"\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Profile 'a' in '.envmn.toml' must be a list of block names"
+        );
+    }
+}
@@ -3,8 +3,16 @@ use std::fmt::{Display, Formatter};
 #[derive(Debug)]
 pub enum AccessErrors {
     FileError(String, String),
+    FileWriteError(String, String),
     BlockNotFound(String),
+    BlockAlreadyExists(String),
+    EnvironmentNotFound(String),
+    VariableNotFound(String, String),
+    VariableNotDefined(String),
+    CommandError(String, String),
     DefaultBlockNotMovable,
+    DefaultBlockReserved,
+    DefaultBlockAlwaysEnabled,
 }
 
 impl Display for AccessErrors {
@@ -13,12 +21,39 @@ impl Display for AccessErrors {
             AccessErrors::FileError(file_path, error) => {
                 write!(f, "Error reading file '{file_path}': {error}")
             }
+            AccessErrors::FileWriteError(file_path, error) => {
+                write!(f, "Error writing file '{file_path}': {error}")
+            }
             AccessErrors::BlockNotFound(block_name) => {
                 write!(f, "Block '{block_name}' was not found")
             }
+            AccessErrors::BlockAlreadyExists(block_name) => {
+                write!(f, "Block '{block_name}' already exists")
+            }
+            AccessErrors::EnvironmentNotFound(environment) => {
+                write!(f, "No block belongs to environment '{environment}'")
+            }
+            AccessErrors::VariableNotFound(name, block_name) => {
+                write!(f, "Variable '{name}' was not found in block '{block_name}'")
+            }
+            AccessErrors::VariableNotDefined(name) => {
+                write!(f, "Variable '{name}' is not defined in any block")
+            }
+            AccessErrors::CommandError(program, error) => {
+                write!(f, "Error running '{program}': {error}")
+            }
             AccessErrors::DefaultBlockNotMovable => {
                 write!(f, "default block is not movable")
             }
+            AccessErrors::DefaultBlockReserved => {
+                write!(
+                    f,
+                    "default block cannot be created, renamed, copied or deleted"
+                )
+            }
+            AccessErrors::DefaultBlockAlwaysEnabled => {
+                write!(f, "default block is always enabled")
+            }
         }
     }
 }
@@ -4,6 +4,9 @@ use std::fmt::{Display, Formatter};
 pub enum CliErrors {
     NoOperationFound,
     NoInputFound,
+    InvalidRuleLevel(String),
+    UnknownRule(String),
+    UnfixableErrors,
 }
 
 impl Display for CliErrors {
@@ -15,6 +18,21 @@ impl Display for CliErrors {
             CliErrors::NoInputFound => {
                 write!(f, "No input found")
             }
+            CliErrors::InvalidRuleLevel(arg) => {
+                write!(
+                    f,
+                    "Invalid rule setting '{arg}', expected RULE=off|info|warning|error"
+                )
+            }
+            CliErrors::UnknownRule(rule) => {
+                write!(f, "Unknown lint rule '{rule}'")
+            }
+            CliErrors::UnfixableErrors => {
+                write!(
+                    f,
+                    "The file has errors that cannot be fixed automatically, nothing was written"
+                )
+            }
         }
     }
 }
@@ -0,0 +1,38 @@
+use crate::parser::Span;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug)]
+pub enum ConfigErrors {
+    InvalidConfig(String, Option<Span>, String),
+    InvalidProfile(String, String),
+    ProfileNotFound(String, String),
+    UnknownBlock(String, String),
+}
+
+impl Display for ConfigErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigErrors::InvalidConfig(path, Some(span), message) => {
+                write!(f, "Invalid config '{path}' at {span}: {message}")
+            }
+            ConfigErrors::InvalidConfig(path, None, message) => {
+                write!(f, "Invalid config '{path}': {message}")
+            }
+            ConfigErrors::InvalidProfile(path, name) => {
+                write!(
+                    f,
+                    "Profile '{name}' in '{path}' must be a list of block names"
+                )
+            }
+            ConfigErrors::ProfileNotFound(path, name) => {
+                write!(f, "Profile '{name}' is not defined in '{path}'")
+            }
+            ConfigErrors::UnknownBlock(name, block_name) => {
+                write!(
+                    f,
+                    "Profile '{name}' picks block '{block_name}', which is not in the file"
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use crate::error::{Error, LintErrors};
+use crate::parser::{Fix, Span};
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// Exit code of a run whose worst finding has this severity.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Severity::Info => 0,
+            Severity::Warning => 1,
+            Severity::Error => 2,
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub rule: Option<&'static str>,
+    pub error: Error,
+    pub span: Option<Span>,
+    pub fix: Option<Fix>,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(span) = self.span {
+            write!(f, "{span}: ")?;
+        }
+        match self.rule {
+            Some(rule) => write!(f, "{0}[{rule}]: {1}", self.severity, self.error),
+            None => write!(f, "{0}: {1}", self.severity, self.error),
+        }
+    }
+}
+
+/// Everything found while reading a document, in the order it was found.
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Diagnostics::default()
+    }
+    pub fn push_error(&mut self, error: Error) {
+        self.push(Severity::Error, error);
+    }
+    pub fn push_warning(&mut self, error: Error) {
+        self.push(Severity::Warning, error);
+    }
+    /// An error the parser recovered from in a way `lint --fix` can keep.
+    pub fn push_fixable(&mut self, error: Error, fix: Fix) {
+        self.diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            rule: None,
+            span: error.span(),
+            error,
+            fix: Some(fix),
+        });
+    }
+    pub fn push_lint(
+        &mut self,
+        rule: &'static str,
+        severity: Severity,
+        error: LintErrors,
+        span: Option<Span>,
+        fix: Option<Fix>,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            rule: Some(rule),
+            error: Error::LintError(error),
+            span,
+            fix,
+        });
+    }
+    pub fn has_errors(&self) -> bool {
+        self.max_severity() == Some(Severity::Error)
+    }
+    /// Whether the parser skipped something it has no fix for. Such a
+    /// document may be missing lines and must not be written back.
+    pub fn has_unfixable_errors(&self) -> bool {
+        self.diagnostics.iter().any(|diagnostic| {
+            diagnostic.severity == Severity::Error
+                && diagnostic.rule.is_none()
+                && diagnostic.fix.is_none()
+        })
+    }
+    /// Removes every diagnostic that has a fix and returns the fixes in the
+    /// order they should be applied.
+    pub fn take_fixes(&mut self) -> Vec<Fix> {
+        let (fixable, rest) = std::mem::take(&mut self.diagnostics)
+            .into_iter()
+            .partition::<Vec<_>, _>(|diagnostic| diagnostic.fix.is_some());
+        self.diagnostics = rest;
+        let mut fixes = fixable
+            .into_iter()
+            .filter_map(|diagnostic| diagnostic.fix)
+            .collect::<Vec<_>>();
+        fixes.sort();
+        fixes.dedup();
+        fixes
+    }
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter()
+    }
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.severity)
+            .max()
+    }
+    fn push(&mut self, severity: Severity, error: Error) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            rule: None,
+            span: error.span(),
+            error,
+            fix: None,
+        });
+    }
+}
+
+impl Display for Diagnostics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{diagnostic}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParsingErrors;
+
+    #[test]
+    fn severity_order() {
+        assert!(Severity::Error > Severity::Warning);
+        assert!(Severity::Warning > Severity::Info);
+    }
+
+    #[test]
+    fn max_severity() {
+        let mut diagnostics = Diagnostics::new();
+        assert_eq!(diagnostics.max_severity(), None);
+        diagnostics.push_lint(
+            "empty-block",
+            Severity::Info,
+            LintErrors::EmptyBlock("test".to_string()),
+            None,
+            None,
+        );
+        assert_eq!(diagnostics.max_severity(), Some(Severity::Info));
+        diagnostics.push_warning(Error::ParsingError(ParsingErrors::TextAfterBlockEnd(
+            Span::default(),
+        )));
+        assert_eq!(diagnostics.max_severity(), Some(Severity::Warning));
+        assert_eq!(
+            diagnostics.to_string(),
+            "info[empty-block]: Block 'test' has no variables\n1:1: warning: Text after block end is ignored\n"
+        );
+    }
+
+    #[test]
+    fn collects_in_order() {
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.push_warning(Error::ParsingError(ParsingErrors::TextAfterBlockEnd(
+            Span::default(),
+        )));
+        assert!(!diagnostics.has_errors());
+        diagnostics.push_error(Error::ParsingError(ParsingErrors::MissingEqSeparator(
+            Span::new(4, 1, 0),
+        )));
+        assert!(diagnostics.has_errors());
+        assert_eq!(
+            diagnostics.to_string(),
+            "1:1: warning: Text after block end is ignored\n2:1: error: Missing key and optional value separator\n"
+        );
+    }
+
+    #[test]
+    fn take_fixes() {
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.push_lint(
+            "empty-block",
+            Severity::Warning,
+            LintErrors::EmptyBlock("test".to_string()),
+            Some(Span::new(0, 0, 0)),
+            Some(Fix::RemoveBlock("test".to_string())),
+        );
+        diagnostics.push_fixable(
+            Error::ParsingError(ParsingErrors::UnterminatedQuote(Span::default())),
+            Fix::NormalizeVariable("default".to_string(), "A".to_string()),
+        );
+        assert!(!diagnostics.has_unfixable_errors());
+        diagnostics.push_error(Error::ParsingError(ParsingErrors::MissingEqSeparator(
+            Span::new(4, 1, 0),
+        )));
+        assert!(diagnostics.has_unfixable_errors());
+        assert_eq!(
+            diagnostics.take_fixes(),
+            vec![
+                Fix::NormalizeVariable("default".to_string(), "A".to_string()),
+                Fix::RemoveBlock("test".to_string()),
+            ]
+        );
+        assert_eq!(
+            diagnostics.to_string(),
+            "2:1: error: Missing key and optional value separator\n"
+        );
+    }
+}
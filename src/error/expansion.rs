@@ -0,0 +1,35 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug)]
+pub enum ExpansionErrors {
+    InvalidReference(String, String),
+    RequiredVariable(String, String),
+    CircularReference(Vec<String>),
+}
+
+impl Display for ExpansionErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExpansionErrors::InvalidReference(name, reference) => {
+                write!(
+                    f,
+                    "Variable '{name}' has an invalid reference '{reference}'"
+                )
+            }
+            ExpansionErrors::RequiredVariable(name, message) if message.is_empty() => {
+                write!(f, "Variable '{name}' is not set")
+            }
+            ExpansionErrors::RequiredVariable(name, message) => {
+                write!(f, "Variable '{name}' is not set: {message}")
+            }
+            ExpansionErrors::CircularReference(chain) => {
+                write!(
+                    f,
+                    "Variable '{0}' refers to itself: {1}",
+                    chain[0],
+                    chain.join(" -> ")
+                )
+            }
+        }
+    }
+}
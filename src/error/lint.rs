@@ -0,0 +1,142 @@
+use crate::parser::Span;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug)]
+pub enum LintErrors {
+    EmptyBlock(String),
+    DuplicateValue(String, String),
+    TrailingWhitespace(String, String),
+    LowercaseKey(String, String),
+    KeySpacing(String, String),
+    UnusedDirective(String, Option<String>),
+    UndefinedReference(Span, String, String, String),
+    CircularReference(Span, Vec<String>, String),
+    GroupKeys(Vec<String>, String, String),
+}
+
+impl LintErrors {
+    pub fn block_name(&self) -> &str {
+        match self {
+            LintErrors::EmptyBlock(block_name)
+            | LintErrors::DuplicateValue(_, block_name)
+            | LintErrors::TrailingWhitespace(_, block_name)
+            | LintErrors::LowercaseKey(_, block_name)
+            | LintErrors::KeySpacing(_, block_name)
+            | LintErrors::UnusedDirective(block_name, _)
+            | LintErrors::UndefinedReference(_, _, _, block_name)
+            | LintErrors::CircularReference(_, _, block_name)
+            | LintErrors::GroupKeys(_, _, block_name) => block_name,
+        }
+    }
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            LintErrors::DuplicateValue(name, _)
+            | LintErrors::TrailingWhitespace(name, _)
+            | LintErrors::LowercaseKey(name, _)
+            | LintErrors::KeySpacing(name, _)
+            | LintErrors::UndefinedReference(_, _, name, _) => Some(name),
+            LintErrors::CircularReference(_, chain, _) => Some(&chain[0]),
+            LintErrors::EmptyBlock(_)
+            | LintErrors::UnusedDirective(..)
+            | LintErrors::GroupKeys(..) => None,
+        }
+    }
+    /// Where the finding is, for those more precise than their variable.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            LintErrors::UndefinedReference(span, ..) | LintErrors::CircularReference(span, ..) => {
+                Some(*span)
+            }
+            _ => None,
+        }
+    }
+    pub fn help(&self) -> String {
+        match self {
+            LintErrors::EmptyBlock(_) => "add variables to it or remove it".to_string(),
+            LintErrors::DuplicateValue(_, _) => {
+                "remove it, the default block already sets this value".to_string()
+            }
+            LintErrors::TrailingWhitespace(_, _) => {
+                "remove the whitespace, or quote the value if it is meant".to_string()
+            }
+            LintErrors::LowercaseKey(name, _) => {
+                format!("rename it to '{0}'", name.to_ascii_uppercase())
+            }
+            LintErrors::KeySpacing(name, _) => format!("write it as '{name}=...'"),
+            LintErrors::UnusedDirective(_, _) => "remove the directive".to_string(),
+            LintErrors::UndefinedReference(_, reference, _, _) => {
+                format!("define '{reference}', or give it a default with '${{{reference}:-value}}'")
+            }
+            LintErrors::CircularReference(_, _, _) => {
+                "replace one of the references with a value".to_string()
+            }
+            LintErrors::GroupKeys(_, _, _) => {
+                "set the keys here too, or remove them from the other blocks".to_string()
+            }
+        }
+    }
+}
+
+impl Display for LintErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LintErrors::EmptyBlock(block_name) => {
+                write!(f, "Block '{block_name}' has no variables")
+            }
+            LintErrors::DuplicateValue(name, block_name) => {
+                write!(
+                    f,
+                    "Variable '{name}' in block '{block_name}' repeats the value from the default block"
+                )
+            }
+            LintErrors::TrailingWhitespace(name, block_name) => {
+                write!(
+                    f,
+                    "Value of '{name}' in block '{block_name}' has trailing whitespace"
+                )
+            }
+            LintErrors::LowercaseKey(name, block_name) => {
+                write!(
+                    f,
+                    "Variable '{name}' in block '{block_name}' is not uppercase"
+                )
+            }
+            LintErrors::KeySpacing(name, block_name) => {
+                write!(
+                    f,
+                    "Variable '{name}' in block '{block_name}' has spaces around '='"
+                )
+            }
+            LintErrors::UnusedDirective(block_name, Some(rule)) => {
+                write!(
+                    f,
+                    "Directive in block '{block_name}' disables '{rule}' but nothing was reported"
+                )
+            }
+            LintErrors::UnusedDirective(block_name, None) => {
+                write!(f, "Directive in block '{block_name}' suppresses nothing")
+            }
+            LintErrors::UndefinedReference(_, reference, name, block_name) => {
+                write!(
+                    f,
+                    "Variable '{name}' in block '{block_name}' refers to '{reference}', which is not defined"
+                )
+            }
+            LintErrors::CircularReference(_, chain, block_name) => {
+                write!(
+                    f,
+                    "Variable '{0}' in block '{block_name}' refers to itself: {1}",
+                    chain[0],
+                    chain.join(" -> ")
+                )
+            }
+            LintErrors::GroupKeys(keys, group, block_name) => {
+                write!(
+                    f,
+                    "Block '{block_name}' of group '{group}' does not set {0}, which other blocks of the group do",
+                    keys.join(", ")
+                )
+            }
+        }
+    }
+}
@@ -1,22 +1,73 @@
 mod access;
 mod cli;
+mod config;
+mod diagnostic;
+mod expansion;
+mod lint;
 mod naming;
 mod parsing;
+mod render;
+mod report;
 
 pub use access::AccessErrors;
 pub use cli::CliErrors;
+pub use config::ConfigErrors;
+pub use diagnostic::{Diagnostics, Severity};
+pub use expansion::ExpansionErrors;
+pub use lint::LintErrors;
 pub use naming::NamingErrors;
 pub use parsing::ParsingErrors;
+pub use render::Renderer;
+pub use report::{report, span_json};
+use crate::parser::Span;
 use std::fmt::{Display, Formatter};
 
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum Error {
     AccessError(AccessErrors),
     NamingError(NamingErrors),
     ParsingError(ParsingErrors),
+    LintError(LintErrors),
+    ExpansionError(ExpansionErrors),
+    ConfigError(ConfigErrors),
     CliError(CliErrors),
 }
 
+impl Error {
+    /// Where in the source the error was found, when it comes from there.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Error::ParsingError(err) => Some(err.span()),
+            Error::NamingError(err) => Some(err.span()),
+            _ => None,
+        }
+    }
+    /// The block the error is about, when it names one.
+    pub fn block_name(&self) -> Option<&str> {
+        match self {
+            Error::ParsingError(err) => err.block_name(),
+            Error::LintError(err) => Some(err.block_name()),
+            _ => None,
+        }
+    }
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            Error::ParsingError(err) => err.key(),
+            Error::LintError(err) => err.key(),
+            _ => None,
+        }
+    }
+    pub fn help(&self) -> Option<String> {
+        match self {
+            Error::ParsingError(err) => err.help(),
+            Error::NamingError(err) => err.help(),
+            Error::LintError(err) => Some(err.help()),
+            _ => None,
+        }
+    }
+}
+
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -26,6 +77,9 @@ impl Display for Error {
                 Error::AccessError(err) => err.to_string(),
                 Error::NamingError(err) => err.to_string(),
                 Error::ParsingError(err) => err.to_string(),
+                Error::LintError(err) => err.to_string(),
+                Error::ExpansionError(err) => err.to_string(),
+                Error::ConfigError(err) => err.to_string(),
                 Error::CliError(err) => err.to_string(),
             }
         )
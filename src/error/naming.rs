@@ -1,43 +1,85 @@
+use crate::parser::Span;
 use std::fmt::{Display, Formatter};
 
 #[derive(Debug)]
 pub enum NamingErrors {
-    BlockNameEmpty,
-    VariableNameEmpty,
-    BlockContainsInvalidCharacter(u16, String),
-    VariableContainsInvalidCharacter(u16, String),
-    StartsWithInvalidCharacter(u16, String),
+    BlockNameEmpty(Span),
+    VariableNameEmpty(Span),
+    BlockContainsInvalidCharacter(Span, String),
+    VariableContainsInvalidCharacter(Span, String),
+    StartsWithInvalidCharacter(Span, String),
+}
+
+impl NamingErrors {
+    pub fn span(&self) -> Span {
+        match self {
+            NamingErrors::BlockNameEmpty(span)
+            | NamingErrors::VariableNameEmpty(span)
+            | NamingErrors::BlockContainsInvalidCharacter(span, _)
+            | NamingErrors::VariableContainsInvalidCharacter(span, _)
+            | NamingErrors::StartsWithInvalidCharacter(span, _) => *span,
+        }
+    }
+    pub fn help(&self) -> Option<String> {
+        let help = match self {
+            NamingErrors::BlockNameEmpty(_) => "add a name after '#@'".to_string(),
+            NamingErrors::VariableNameEmpty(_) => "add a name before '='".to_string(),
+            NamingErrors::BlockContainsInvalidCharacter(_, invalid) if invalid == "-" => {
+                "use '_' instead of '-'".to_string()
+            }
+            NamingErrors::BlockContainsInvalidCharacter(_, invalid)
+                if invalid.chars().all(|c| c.is_ascii_uppercase()) =>
+            {
+                format!(
+                    "block names are lowercase, use '{0}' instead of '{invalid}'",
+                    invalid.to_ascii_lowercase()
+                )
+            }
+            NamingErrors::BlockContainsInvalidCharacter(_, _) => {
+                "block names may only contain lowercase letters, digits and '_'".to_string()
+            }
+            NamingErrors::VariableContainsInvalidCharacter(_, invalid) if invalid == "-" => {
+                "use '_' instead of '-'".to_string()
+            }
+            NamingErrors::VariableContainsInvalidCharacter(_, _) => {
+                "variable names may only contain letters, digits and '_'".to_string()
+            }
+            NamingErrors::StartsWithInvalidCharacter(_, invalid)
+                if invalid.chars().all(|c| c.is_ascii_uppercase()) =>
+            {
+                format!(
+                    "block names are lowercase, use '{0}' instead of '{invalid}'",
+                    invalid.to_ascii_lowercase()
+                )
+            }
+            NamingErrors::StartsWithInvalidCharacter(_, _) => {
+                "names must start with a letter or '_'".to_string()
+            }
+        };
+        Some(help)
+    }
 }
 
 impl Display for NamingErrors {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            NamingErrors::BlockNameEmpty => {
+            NamingErrors::BlockNameEmpty(_) => {
                 write!(f, "Block name can not be empty")
             }
-            NamingErrors::VariableNameEmpty => {
+            NamingErrors::VariableNameEmpty(_) => {
                 write!(f, "Variable name can not be empty")
             }
-            NamingErrors::BlockContainsInvalidCharacter(line, invalid_char) => {
-                write!(
-                    f,
-                    "Line {0}: Block name contains invalid characters '{invalid_char}'",
-                    line + 1
-                )
+            NamingErrors::BlockContainsInvalidCharacter(_, invalid_char) => {
+                write!(f, "Block name contains invalid characters '{invalid_char}'")
             }
-            NamingErrors::VariableContainsInvalidCharacter(line, invalid_char) => {
+            NamingErrors::VariableContainsInvalidCharacter(_, invalid_char) => {
                 write!(
                     f,
-                    "Line {0}: Variable name contains invalid characters '{invalid_char}'",
-                    line + 1
+                    "Variable name contains invalid characters '{invalid_char}'"
                 )
             }
-            NamingErrors::StartsWithInvalidCharacter(line, invalids) => {
-                write!(
-                    f,
-                    "Line {0}: Block name starts with invalid character '{invalids}'",
-                    line + 1
-                )
+            NamingErrors::StartsWithInvalidCharacter(_, invalids) => {
+                write!(f, "Block name starts with invalid character '{invalids}'")
             }
         }
     }
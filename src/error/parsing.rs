@@ -1,51 +1,148 @@
+use crate::parser::Span;
 use std::fmt::{Display, Formatter};
 
 #[derive(Debug)]
 pub enum ParsingErrors {
-    MissingEqSeparator(u16),
-    NestedBlock(u16, String),
-    EmptyInput,
-    BlockNeverOpened(u16),
-    ReservedWord(u16, String),
-    DuplicateBlock(String),
-    DuplicateVariable(String, String),
+    MissingEqSeparator(Span),
+    NestedBlock(Span, String),
+    EmptyInput(Span),
+    BlockNeverOpened(Span),
+    ReservedWord(Span, String),
+    DuplicateBlock(Span, String),
+    DuplicateVariable(Span, String, String),
+    UnterminatedQuote(Span),
+    UnexpectedCharacters(Span),
+    TextAfterBlockEnd(Span),
+    UnclosedBlock(Span, String),
+    UnknownBlockAttribute(Span, String),
+    LiveVariableInDisabledBlock(Span, String, String),
+}
+
+impl ParsingErrors {
+    pub fn span(&self) -> Span {
+        match self {
+            ParsingErrors::MissingEqSeparator(span)
+            | ParsingErrors::NestedBlock(span, _)
+            | ParsingErrors::EmptyInput(span)
+            | ParsingErrors::BlockNeverOpened(span)
+            | ParsingErrors::ReservedWord(span, _)
+            | ParsingErrors::DuplicateBlock(span, _)
+            | ParsingErrors::DuplicateVariable(span, _, _)
+            | ParsingErrors::UnterminatedQuote(span)
+            | ParsingErrors::UnexpectedCharacters(span)
+            | ParsingErrors::TextAfterBlockEnd(span)
+            | ParsingErrors::UnclosedBlock(span, _)
+            | ParsingErrors::UnknownBlockAttribute(span, _)
+            | ParsingErrors::LiveVariableInDisabledBlock(span, _, _) => *span,
+        }
+    }
+    pub fn block_name(&self) -> Option<&str> {
+        match self {
+            ParsingErrors::NestedBlock(_, name)
+            | ParsingErrors::DuplicateBlock(_, name)
+            | ParsingErrors::DuplicateVariable(_, _, name)
+            | ParsingErrors::UnclosedBlock(_, name)
+            | ParsingErrors::LiveVariableInDisabledBlock(_, _, name) => Some(name),
+            _ => None,
+        }
+    }
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            ParsingErrors::DuplicateVariable(_, key, _)
+            | ParsingErrors::LiveVariableInDisabledBlock(_, key, _) => Some(key),
+            _ => None,
+        }
+    }
+    pub fn help(&self) -> Option<String> {
+        let help = match self {
+            ParsingErrors::MissingEqSeparator(_) => {
+                "write it as KEY=value, or start the line with '#' to make it a comment".to_string()
+            }
+            ParsingErrors::NestedBlock(_, name) => {
+                format!("close block '{name}' with '##' before opening another one")
+            }
+            ParsingErrors::EmptyInput(_) => return None,
+            ParsingErrors::BlockNeverOpened(_) => {
+                "remove it, or open a block above it with '#@ name'".to_string()
+            }
+            ParsingErrors::ReservedWord(_, name) => {
+                format!("'{name}' stands for the lines outside of any block, pick another name")
+            }
+            ParsingErrors::DuplicateBlock(_, _) => {
+                "merge the two blocks or rename one of them".to_string()
+            }
+            ParsingErrors::DuplicateVariable(_, _, _) => "remove one of the two".to_string(),
+            ParsingErrors::UnterminatedQuote(_) => {
+                "close the quote where the value ends".to_string()
+            }
+            ParsingErrors::UnexpectedCharacters(_) => {
+                "only a '#' comment may follow the closing quote".to_string()
+            }
+            ParsingErrors::TextAfterBlockEnd(_) => {
+                "move the text to a '#' comment on a line of its own".to_string()
+            }
+            ParsingErrors::UnclosedBlock(_, _) => {
+                "add '##' on the line after its last variable".to_string()
+            }
+            ParsingErrors::UnknownBlockAttribute(_, _) => {
+                "only 'group=name' may follow the block name".to_string()
+            }
+            ParsingErrors::LiveVariableInDisabledBlock(_, _, name) => {
+                format!("run 'envmn disable {name}' to comment it out with the rest")
+            }
+        };
+        Some(help)
+    }
 }
 
 impl Display for ParsingErrors {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            ParsingErrors::MissingEqSeparator(line) => {
-                write!(
-                    f,
-                    "Line {0}: Missing key and optional value separator",
-                    line + 1
-                )
+            ParsingErrors::MissingEqSeparator(_) => {
+                write!(f, "Missing key and optional value separator")
             }
-            ParsingErrors::NestedBlock(line, name) => {
-                write!(
-                    f,
-                    "Line {0}: Block '{name}' can not wrap another block",
-                    line + 1
-                )
+            ParsingErrors::NestedBlock(_, name) => {
+                write!(f, "Block '{name}' can not wrap another block")
             }
-            ParsingErrors::EmptyInput => {
+            ParsingErrors::EmptyInput(_) => {
                 write!(f, "Empty input")
             }
-            ParsingErrors::BlockNeverOpened(line) => {
-                write!(f, "Line {0}: Closed block was never opened", line + 1)
+            ParsingErrors::BlockNeverOpened(_) => {
+                write!(f, "Closed block was never opened")
             }
-            ParsingErrors::ReservedWord(line, name) => {
-                write!(f, "Line {0}: You can not use keyword '{name}'", line + 1)
+            ParsingErrors::ReservedWord(_, name) => {
+                write!(f, "You can not use keyword '{name}'")
             }
-            ParsingErrors::DuplicateBlock(name) => {
+            ParsingErrors::DuplicateBlock(_, name) => {
                 write!(f, "Duplicate block '{name}' found")
             }
-            ParsingErrors::DuplicateVariable(name, token_name) => {
+            ParsingErrors::DuplicateVariable(_, name, token_name) => {
                 write!(
                     f,
                     "Duplicate variable '{name}' found in block '{token_name}'"
                 )
             }
+            ParsingErrors::UnterminatedQuote(_) => {
+                write!(f, "Quoted value is never closed")
+            }
+            ParsingErrors::UnexpectedCharacters(_) => {
+                write!(f, "Unexpected characters after closing quote")
+            }
+            ParsingErrors::TextAfterBlockEnd(_) => {
+                write!(f, "Text after block end is ignored")
+            }
+            ParsingErrors::UnclosedBlock(_, name) => {
+                write!(f, "Block '{name}' is never closed")
+            }
+            ParsingErrors::UnknownBlockAttribute(_, attribute) => {
+                write!(f, "Unknown block attribute '{attribute}'")
+            }
+            ParsingErrors::LiveVariableInDisabledBlock(_, key, name) => {
+                write!(
+                    f,
+                    "Variable '{key}' is not commented out, loaders read it although block '{name}' is disabled"
+                )
+            }
         }
     }
 }
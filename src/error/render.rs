@@ -0,0 +1,144 @@
+use crate::error::diagnostic::Diagnostic;
+use crate::error::{Diagnostics, Severity};
+use crate::parser::Span;
+use std::env;
+use std::io::{IsTerminal, stderr};
+
+/// Prints diagnostics the way rustc does: the message, the place it was found,
+/// the source line with a caret under the offending character and a hint on
+/// how to resolve it. Colour is only used on a terminal without `NO_COLOR`.
+pub struct Renderer<'a> {
+    file_name: &'a str,
+    source: &'a str,
+    color: bool,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(file_name: &'a str, source: &'a str) -> Self {
+        Renderer {
+            file_name,
+            source,
+            color: stderr().is_terminal()
+                && env::var_os("NO_COLOR").is_none_or(|value| value.is_empty()),
+        }
+    }
+    pub fn render(&self, diagnostics: &Diagnostics) -> String {
+        diagnostics
+            .iter()
+            .map(|diagnostic| self.render_diagnostic(diagnostic))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+    fn render_diagnostic(&self, diagnostic: &Diagnostic) -> String {
+        let style = match diagnostic.severity {
+            Severity::Error => "1;31",
+            Severity::Warning => "1;33",
+            Severity::Info => "1;36",
+        };
+        let title = match diagnostic.rule {
+            Some(rule) => format!("{0}[{rule}]", diagnostic.severity),
+            None => diagnostic.severity.to_string(),
+        };
+        let mut out = format!(
+            "{0}{1}\n",
+            self.paint(&title, style),
+            self.paint(&format!(": {}", diagnostic.error), "1")
+        );
+        let Some(span) = diagnostic.span else {
+            out.push_str(&format!(
+                " {0} {1}\n",
+                self.paint("-->", "1;34"),
+                self.file_name
+            ));
+            self.push_notes(&mut out, diagnostic, " ");
+            return out;
+        };
+        let number = (span.line + 1).to_string();
+        let pad = " ".repeat(number.len());
+        let bar = self.paint("|", "1;34");
+        let line = self.line_at(span);
+        let indent = line
+            .chars()
+            .take(span.column)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        out.push_str(&format!(
+            "{pad}{0} {1}:{span}\n",
+            self.paint("-->", "1;34"),
+            self.file_name
+        ));
+        out.push_str(&format!("{pad} {bar}\n"));
+        out.push_str(&format!("{0} {bar} {line}\n", self.paint(&number, "1;34")));
+        out.push_str(&format!(
+            "{pad} {bar} {indent}{0}\n",
+            self.paint("^", style)
+        ));
+        self.push_notes(&mut out, diagnostic, &pad);
+        out
+    }
+    fn push_notes(&self, out: &mut String, diagnostic: &Diagnostic, pad: &str) {
+        if let Some(help) = diagnostic.error.help() {
+            out.push_str(&format!("{pad} = {0}: {help}\n", self.paint("help", "1")));
+        }
+        if diagnostic.fix.is_some() {
+            out.push_str(&format!(
+                "{pad} = {0}: `envmn lint --fix` can fix this\n",
+                self.paint("note", "1")
+            ));
+        }
+    }
+    /// The whole source line `span` points into, without its line break.
+    fn line_at(&self, span: Span) -> &str {
+        let offset = span.offset.min(self.source.len());
+        let start = self.source[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let end = self.source[offset..]
+            .find('\n')
+            .map_or(self.source.len(), |idx| offset + idx);
+        self.source[start..end].trim_end_matches('\r')
+    }
+    fn paint(&self, text: &str, style: &str) -> String {
+        if self.color {
+            format!("\x1b[{style}m{text}\x1b[0m")
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn render(source: &str) -> String {
+        let (_, diagnostics) = Parser::lossless().parse(source);
+        Renderer {
+            file_name: ".env",
+            source,
+            color: false,
+        }
+        .render(&diagnostics)
+    }
+
+    #[test]
+    fn caret_under_offending_character() {
+        assert_eq!(
+            render("A=1\n#@ dev\n\tbad-key=1\n##\n"),
+            "error: Variable name contains invalid characters '-'\n --> .env:3:5\n  |\n3 | \tbad-key=1\n  | \t   ^\n  = help: use '_' instead of '-'\n"
+        );
+    }
+
+    #[test]
+    fn fixable_errors_get_a_note() {
+        let rendered = render("A=\"open\n");
+        assert!(rendered.starts_with("error: Quoted value is never closed\n --> .env:1:3\n"));
+        assert!(rendered.ends_with("  = note: `envmn lint --fix` can fix this\n"));
+    }
+
+    #[test]
+    fn blank_line_between_diagnostics() {
+        let rendered = render("NO_SEPARATOR\n##\n");
+        assert!(rendered.contains("1 | NO_SEPARATOR\n  | ^\n  = help"));
+        assert!(rendered.contains("\n\nerror: Closed block was never opened\n --> .env:2:1\n"));
+    }
+}
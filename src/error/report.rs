@@ -0,0 +1,139 @@
+use crate::cli::OutputFormat;
+use crate::error::diagnostic::Diagnostic;
+use crate::error::{Diagnostics, Renderer, Severity};
+use crate::parser::Span;
+use serde_json::{Value, json};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const INFORMATION_URI: &str = "https://github.com/devark28/envmn";
+
+/// Prints diagnostics in the requested format: rendered for people on
+/// stderr, or as one JSON or SARIF document on stdout for editors and CI.
+pub fn report(format: OutputFormat, file_name: &str, source: &str, diagnostics: &Diagnostics) {
+    match format {
+        OutputFormat::Text => eprint!("{}", Renderer::new(file_name, source).render(diagnostics)),
+        OutputFormat::Json => println!("{}", to_json(file_name, diagnostics)),
+        OutputFormat::Sarif => println!("{}", to_sarif(file_name, diagnostics)),
+    }
+}
+
+/// Spans are written the way editors count: lines and columns from 1.
+pub fn span_json(span: Span) -> Value {
+    json!({
+        "offset": span.offset,
+        "line": span.line + 1,
+        "column": span.column + 1,
+    })
+}
+
+fn to_json(file_name: &str, diagnostics: &Diagnostics) -> Value {
+    json!({
+        "file": file_name,
+        "diagnostics": diagnostics.iter().map(diagnostic_json).collect::<Vec<_>>(),
+    })
+}
+
+fn diagnostic_json(diagnostic: &Diagnostic) -> Value {
+    json!({
+        "severity": diagnostic.severity.to_string(),
+        "rule": diagnostic.rule,
+        "message": diagnostic.error.to_string(),
+        "help": diagnostic.error.help(),
+        "span": diagnostic.span.map(span_json),
+        "block": diagnostic.error.block_name(),
+        "key": diagnostic.error.key(),
+        "fixable": diagnostic.fix.is_some(),
+    })
+}
+
+fn to_sarif(file_name: &str, diagnostics: &Diagnostics) -> Value {
+    let mut rules = diagnostics
+        .iter()
+        .filter_map(|diagnostic| diagnostic.rule)
+        .collect::<Vec<_>>();
+    rules.sort();
+    rules.dedup();
+    let results = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let mut location = json!({ "artifactLocation": { "uri": file_name } });
+            if let Some(span) = diagnostic.span {
+                location["region"] = json!({
+                    "startLine": span.line + 1,
+                    "startColumn": span.column + 1,
+                });
+            }
+            let mut result = json!({
+                "level": match diagnostic.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                    Severity::Info => "note",
+                },
+                "message": { "text": diagnostic.error.to_string() },
+                "locations": [{ "physicalLocation": location }],
+            });
+            if let Some(rule) = diagnostic.rule {
+                result["ruleId"] = json!(rule);
+            }
+            result
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": INFORMATION_URI,
+                    "rules": rules.iter().map(|rule| json!({ "id": rule })).collect::<Vec<_>>(),
+                }
+            },
+            "results": results,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn json_has_one_based_spans() {
+        let (_, diagnostics) = Parser::lossless().parse("A=1\nNO_SEPARATOR\n");
+        let report = to_json(".env", &diagnostics);
+        assert_eq!(report["file"], ".env");
+        let diagnostic = &report["diagnostics"][0];
+        assert_eq!(diagnostic["severity"], "error");
+        assert_eq!(diagnostic["rule"], Value::Null);
+        assert_eq!(
+            diagnostic["span"],
+            json!({"offset": 4, "line": 2, "column": 1})
+        );
+        assert_eq!(diagnostic["fixable"], false);
+    }
+
+    #[test]
+    fn json_names_block_and_key() {
+        let (_, diagnostics) = Parser::lossless().parse("#@ dev\nA=1\nA=2\n##\n");
+        let diagnostic = &to_json(".env", &diagnostics)["diagnostics"][0];
+        assert_eq!(diagnostic["block"], "dev");
+        assert_eq!(diagnostic["key"], "A");
+    }
+
+    #[test]
+    fn sarif_levels_and_regions() {
+        let (_, diagnostics) = Parser::lossless().parse("A=\"open\n");
+        let report = to_sarif(".env", &diagnostics);
+        assert_eq!(report["version"], "2.1.0");
+        let result = &report["runs"][0]["results"][0];
+        assert_eq!(result["level"], "error");
+        assert!(result.get("ruleId").is_none());
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["region"],
+            json!({"startLine": 1, "startColumn": 3})
+        );
+    }
+}
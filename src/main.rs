@@ -1,9 +1,10 @@
 mod cli;
+mod config;
 mod error;
 mod parser;
 
-use crate::cli::{Cli, Commands, Source};
-use crate::error::{CliErrors, Error};
+use crate::cli::{Cli, Commands, OutputFormat};
+use crate::error::{CliErrors, Diagnostics, Error, report};
 use crate::parser::{Engine, Parser};
 use std::process::exit;
 
@@ -19,11 +20,15 @@ fn main() {
             exit(1);
         }
     };
-    let parser = Parser::new();
-    let result = match &cli {
+    let parser = match cli.command {
+        Commands::Format => Parser::new(),
+        _ => Parser::lossless(),
+    };
+    let input = match &cli {
         Cli {
             input: None,
             command: Commands::Version { name, version },
+            ..
         } => {
             Engine::process_version_cmd(name, version);
             exit(0);
@@ -34,27 +39,37 @@ fn main() {
             exit(1);
         }
         Cli {
-            input: Some(Source::StdIn(content)),
-            ..
-        } => parser.parse(content),
-        Cli {
-            input: Some(Source::FileName(name)),
-            ..
-        } => parser.parse_file(name),
+            input: Some(input), ..
+        } => input,
     };
-    let document = match result {
-        Ok(document) => document,
-        Err(error_type) => {
-            eprintln!("{}", error_type);
-            exit(1);
-        }
+    let output = cli.output;
+    let file_name = input.name().to_string();
+    let source = match input.read() {
+        Ok(source) => source,
+        Err(error_type) => fail(output, &file_name, error_type),
     };
-    let engine = Engine::new(cli, document);
-    match engine.process() {
-        Err(error_type) => {
-            eprintln!("{}", error_type);
-            exit(1);
-        }
-        _ => (),
+    let (document, diagnostics) = parser.parse(&source);
+    // A document with errors may be missing lines (an unclosed block, a
+    // skipped variable), so only lint gets to look at it.
+    if diagnostics.has_errors() && !matches!(cli.command, Commands::Lint { .. }) {
+        report(output, &file_name, &source, &diagnostics);
+        exit(1);
+    }
+    let engine = Engine::new(cli, source, document, diagnostics);
+    if let Err(error_type) = engine.process() {
+        fail(output, &file_name, error_type);
+    }
+}
+
+/// Reports the error that stopped the run and exits. Tools that asked for
+/// JSON or SARIF get it as a diagnostic they can parse.
+fn fail(output: OutputFormat, file_name: &str, error_type: Error) -> ! {
+    if output == OutputFormat::Text {
+        eprintln!("{}", error_type);
+    } else {
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.push_error(error_type);
+        report(output, file_name, "", &diagnostics);
     }
+    exit(1);
 }
@@ -1,7 +1,12 @@
 pub const BLOCK_START_SYMBOL: &str = "#@";
+pub const DISABLED_BLOCK_START_SYMBOL: &str = "#@!";
+pub const DISABLED_VARIABLE_PREFIX: &str = "#! ";
 pub const BLOCK_END_SYMBOL: &str = "##";
 pub const KV_DELIMITER: &str = "=";
 pub const COMMENT_SYMBOL: &str = "#";
 pub const DEFAULT_BLOCK_NAME: &str = "default";
+pub const GROUP_ATTRIBUTE: &str = "group";
+pub const DISABLE_DIRECTIVE: &str = "envmn-disable";
+pub const DISABLE_NEXT_LINE_DIRECTIVE: &str = "envmn-disable-next-line";
 /*pub const BLOCK_NAME_START_PAT: &str = r"^[^a-z_]";
 pub const BLOCK_NAME_MID_PAT: &str = r"[^a-z_0-9]";*/
@@ -0,0 +1,28 @@
+use crate::cli::BlockAction;
+use crate::error::Error;
+use crate::parser::Span;
+use crate::parser::engine::Engine;
+use crate::parser::validators::validate_block_name;
+
+impl Engine {
+    pub fn process_block_cmd(mut self, action: BlockAction) -> Result<(), Error> {
+        match action {
+            BlockAction::New { name } => {
+                validate_block_name(Span::default(), &name)?;
+                self.document.create_block(&name)?;
+            }
+            BlockAction::Rename { from, to } => {
+                validate_block_name(Span::default(), &to)?;
+                self.document.rename_block(&from, &to)?;
+            }
+            BlockAction::Copy { from, to } => {
+                validate_block_name(Span::default(), &to)?;
+                self.document.copy_block(&from, &to)?;
+            }
+            BlockAction::Delete { name } => {
+                self.document.remove_block(&name)?;
+            }
+        }
+        self.write_document()
+    }
+}
@@ -0,0 +1,10 @@
+use crate::error::Error;
+use crate::parser::engine::Engine;
+
+impl Engine {
+    /// The block stays where it is; disabling a disabled block changes nothing.
+    pub fn process_disable_cmd(mut self, block_name: String) -> Result<(), Error> {
+        self.document.set_enabled(&block_name, false)?;
+        self.write_document()
+    }
+}
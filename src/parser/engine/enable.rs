@@ -0,0 +1,10 @@
+use crate::error::Error;
+use crate::parser::engine::Engine;
+
+impl Engine {
+    /// The block stays where it is; enabling an enabled block changes nothing.
+    pub fn process_enable_cmd(mut self, block_name: String) -> Result<(), Error> {
+        self.document.set_enabled(&block_name, true)?;
+        self.write_document()
+    }
+}
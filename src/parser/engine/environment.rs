@@ -0,0 +1,25 @@
+use crate::error::Error;
+use crate::parser::engine::Engine;
+use crate::parser::expansion::Expander;
+use crate::parser::tokens::Block;
+use crate::parser::tokens::variable::Variable;
+
+impl Engine {
+    /// The resolved variables with the value each one ends up with: as
+    /// written, or with its references expanded.
+    pub fn environment(&self, expand: bool) -> Result<Vec<(&Block, &Variable, String)>, Error> {
+        let mut expander = Expander::new(&self.document);
+        self.document
+            .resolve()
+            .into_values()
+            .map(|(block, variable)| {
+                let value = if expand {
+                    expander.value(&variable.key)?
+                } else {
+                    variable.value.clone()
+                };
+                Ok((block, variable, value))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,41 @@
+use crate::error::{AccessErrors, Error};
+use crate::parser::engine::Engine;
+use std::process::Command;
+
+impl Engine {
+    /// Picks are applied to the parsed document only; the file is left as
+    /// it is.
+    pub fn process_exec_cmd(
+        mut self,
+        picks: Vec<String>,
+        command: Vec<String>,
+        expand: bool,
+    ) -> Result<(), Error> {
+        self.document.pick_all(&picks)?;
+        let (program, args) = command.split_first().expect("clap requires a command");
+        let mut child = Command::new(program);
+        child.args(args).envs(
+            self.environment(expand)?
+                .into_iter()
+                .map(|(_, variable, value)| (&variable.key, value)),
+        );
+        let command_error = |error: std::io::Error| {
+            Error::AccessError(AccessErrors::CommandError(
+                program.to_string(),
+                error.to_string(),
+            ))
+        };
+        // Replacing envmn with the command hands it the terminal, its
+        // signals and its exit code as if it had been started directly.
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            Err(command_error(child.exec()))
+        }
+        #[cfg(not(unix))]
+        {
+            let status = child.status().map_err(command_error)?;
+            std::process::exit(status.code().unwrap_or(1));
+        }
+    }
+}
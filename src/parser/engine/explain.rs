@@ -0,0 +1,36 @@
+use crate::error::{AccessErrors, Error};
+use crate::parser::constants::KV_DELIMITER;
+use crate::parser::engine::Engine;
+
+impl Engine {
+    pub fn process_explain_cmd(self, key: String) -> Result<(), Error> {
+        let chain = self.document.explain(&key);
+        if chain.is_empty() {
+            return Err(Error::AccessError(AccessErrors::VariableNotDefined(key)));
+        }
+        let rows = chain
+            .iter()
+            .map(|(block, variable)| {
+                (
+                    format!("line {}", variable.span().line + 1),
+                    block.name.as_str(),
+                    variable.quote.encode(&variable.value),
+                )
+            })
+            .collect::<Vec<_>>();
+        let line_width = rows.iter().map(|row| row.0.len()).max().unwrap_or(0);
+        let name_width = rows.iter().map(|row| row.1.len()).max().unwrap_or(0);
+        match rows.len() {
+            1 => println!("{key} is set in 1 block:"),
+            count => println!("{key} is set in {count} blocks, the last one wins:"),
+        }
+        for (idx, (line, name, value)) in rows.iter().enumerate() {
+            // The winning definition is the one `resolve` prints.
+            let marker = if idx == rows.len() - 1 { '*' } else { ' ' };
+            println!(
+                "{marker} {line:<line_width$}  {name:<name_width$}  {key}{KV_DELIMITER}{value}"
+            );
+        }
+        Ok(())
+    }
+}
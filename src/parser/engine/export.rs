@@ -0,0 +1,12 @@
+use crate::cli::Shell;
+use crate::error::Error;
+use crate::parser::engine::Engine;
+
+impl Engine {
+    pub fn process_export_cmd(self, shell: Shell, expand: bool) -> Result<(), Error> {
+        for (_, variable, value) in self.environment(expand)? {
+            println!("{}", shell.export(&variable.key, &value));
+        }
+        Ok(())
+    }
+}
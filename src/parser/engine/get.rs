@@ -0,0 +1,27 @@
+use crate::error::{AccessErrors, Error};
+use crate::parser::engine::Engine;
+
+impl Engine {
+    /// Prints the value as it is meant, without quotes or escapes.
+    pub fn process_get_cmd(self, key: String, block_name: Option<String>) -> Result<(), Error> {
+        let variable = match &block_name {
+            Some(block_name) => self
+                .document
+                .get_block(block_name)
+                .ok_or_else(|| Error::AccessError(AccessErrors::BlockNotFound(block_name.clone())))?
+                .get_variable(&key)
+                .ok_or_else(|| {
+                    Error::AccessError(AccessErrors::VariableNotFound(
+                        key.clone(),
+                        block_name.clone(),
+                    ))
+                })?,
+            None => match self.document.resolve().get(key.as_str()) {
+                Some((_, variable)) => variable,
+                None => return Err(Error::AccessError(AccessErrors::VariableNotDefined(key))),
+            },
+        };
+        println!("{}", variable.value);
+        Ok(())
+    }
+}
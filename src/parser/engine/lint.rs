@@ -0,0 +1,68 @@
+use crate::cli::Source;
+use crate::error::{CliErrors, Error, Severity, report};
+use crate::parser::engine::Engine;
+use crate::parser::rules::Registry;
+use similar::TextDiff;
+use std::process::exit;
+
+impl Engine {
+    pub fn process_lint_cmd(
+        mut self,
+        rules: Vec<(String, Option<Severity>)>,
+        fix: bool,
+        dry_run: bool,
+        process_env: bool,
+    ) -> Result<(), Error> {
+        let mut registry = Registry::new();
+        if process_env {
+            registry = registry.with_process_env();
+        }
+        for (rule, severity) in rules {
+            registry.set_severity(&rule, severity)?;
+        }
+        registry.check(&self.document, &mut self.diagnostics);
+        if fix {
+            self.apply_fixes(dry_run)?;
+        }
+        self.report();
+        match self.diagnostics.max_severity() {
+            Some(severity) => exit(severity.exit_code()),
+            None => Ok(()),
+        }
+    }
+    /// Applies every fix and writes the result, or prints it as a diff. Only
+    /// the findings that could not be fixed are left to report.
+    fn apply_fixes(&mut self, dry_run: bool) -> Result<(), Error> {
+        if self.diagnostics.has_unfixable_errors() {
+            self.report();
+            return Err(Error::CliError(CliErrors::UnfixableErrors));
+        }
+        let original = self.document.to_string();
+        for fix in self.diagnostics.take_fixes() {
+            fix.apply(&mut self.document)?;
+        }
+        if !dry_run {
+            return self.write_document();
+        }
+        let name = self.input_name();
+        let fixed = self.document.to_string();
+        print!(
+            "{}",
+            TextDiff::from_lines(&original, &fixed)
+                .unified_diff()
+                .header(&format!("a/{name}"), &format!("b/{name}"))
+        );
+        Ok(())
+    }
+    fn report(&self) {
+        report(
+            self.cli.output,
+            self.input_name(),
+            &self.source,
+            &self.diagnostics,
+        );
+    }
+    fn input_name(&self) -> &str {
+        self.cli.input.as_ref().map_or("<stdin>", Source::name)
+    }
+}
@@ -1,16 +1,68 @@
+use crate::cli::OutputFormat;
+use crate::error::span_json;
+use crate::parser::constants::DEFAULT_BLOCK_NAME;
 use crate::parser::engine::Engine;
+use crate::parser::tokens::Block;
+use serde_json::json;
 
 impl Engine {
     pub fn process_list_cmd(self) {
+        if self.cli.output == OutputFormat::Json {
+            return self.print_list_json();
+        }
         println!(
             "Blocks ({}):\n{}",
             self.document.blocks_len(),
             self.document
                 .get_blocks()
                 .iter()
-                .map(|b| format!("- {}", b.name))
+                .map(|b| {
+                    let notes = [
+                        b.group.clone(),
+                        self.is_active(b).then(|| "active".to_string()),
+                        (!b.enabled()).then(|| "disabled".to_string()),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>();
+                    match notes.is_empty() {
+                        true => format!("- {}", b.name),
+                        false => format!("- {} ({})", b.name, notes.join(", ")),
+                    }
+                })
                 .collect::<Vec<_>>()
                 .join("\n")
         )
     }
+    /// The default block has no header, so it has no span either.
+    fn print_list_json(&self) {
+        let blocks = self
+            .document
+            .get_blocks()
+            .iter()
+            .map(|block| {
+                json!({
+                    "name": block.name,
+                    "span": (block.name != DEFAULT_BLOCK_NAME).then(|| span_json(block.span())),
+                    "group": block.group.as_ref().map(|group| json!({
+                        "name": group,
+                        "active": self.is_active(block),
+                    })),
+                    "enabled": block.enabled(),
+                    "variables": block.variables().map(|v| &v.key).collect::<Vec<_>>(),
+                })
+            })
+            .collect::<Vec<_>>();
+        println!("{}", json!({ "blocks": blocks }));
+    }
+    /// Whether a grouped block is the one of its group that wins.
+    fn is_active(&self, block: &Block) -> bool {
+        block.group.as_deref().is_some_and(|group| {
+            self.document
+                .groups()
+                .get(group)
+                .and_then(|blocks| blocks.last())
+                .is_some_and(|last| last.name == block.name)
+        })
+    }
 }
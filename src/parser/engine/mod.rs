@@ -1,28 +1,96 @@
+mod block;
+mod disable;
+mod enable;
+mod environment;
+mod exec;
+mod explain;
+mod export;
 mod format;
+mod get;
+mod lint;
 mod list;
+mod r#move;
 mod pick;
+mod resolve;
+mod set;
+mod status;
+mod switch;
+mod unset;
+mod r#use;
 mod version;
+mod write;
 
-use crate::cli::{Cli, Commands};
-use crate::error::{CliErrors, Error};
+use crate::cli::{Cli, Commands, Source};
+use crate::error::{CliErrors, Diagnostics, Error};
 use crate::parser::tokens::Document;
 
 pub struct Engine {
     cli: Cli,
+    /// The text the document was parsed from, for diagnostics to quote.
+    source: String,
     document: Document,
+    diagnostics: Diagnostics,
 }
 
 impl Engine {
-    pub fn new(cli: Cli, document: Document) -> Self {
-        Engine { cli, document }
+    pub fn new(cli: Cli, source: String, document: Document, diagnostics: Diagnostics) -> Self {
+        Engine {
+            cli,
+            source,
+            document,
+            diagnostics,
+        }
     }
     pub fn process(self) -> Result<(), Error> {
         match self.cli.command.clone() {
-            Commands::Lint => Ok(()),
-            Commands::List => Ok(self.process_list_cmd()),
-            Commands::Format => Ok(self.process_format_cmd()),
-            Commands::Pick { block_name } => Ok(self.process_pick_cmd(block_name)),
+            Commands::Lint {
+                rules,
+                fix,
+                dry_run,
+                process_env,
+            } => self.process_lint_cmd(rules, fix, dry_run, process_env),
+            Commands::List => {
+                self.process_list_cmd();
+                Ok(())
+            }
+            Commands::Format => self.process_format_cmd(),
+            Commands::Pick {
+                block_names,
+                keep_last,
+            } => self.process_pick_cmd(block_names, keep_last),
+            Commands::Switch { environment } => self.process_switch_cmd(environment),
+            Commands::Use { profile } => self.process_use_cmd(profile),
+            Commands::Enable { block_name } => self.process_enable_cmd(block_name),
+            Commands::Disable { block_name } => self.process_disable_cmd(block_name),
+            Commands::Status => self.process_status_cmd(),
+            Commands::Move {
+                block_name,
+                position,
+            } => self.process_move_cmd(block_name, position),
+            Commands::Resolve { annotate, expand } => self.process_resolve_cmd(annotate, expand),
+            Commands::Explain { key } => self.process_explain_cmd(key),
+            Commands::Exec {
+                picks,
+                command,
+                expand,
+            } => self.process_exec_cmd(picks, command, expand),
+            Commands::Export { shell, expand } => self.process_export_cmd(shell, expand),
+            Commands::Get { key, block_name } => self.process_get_cmd(key, block_name),
+            Commands::Set {
+                key,
+                value,
+                block_name,
+            } => self.process_set_cmd(key, value, block_name),
+            Commands::Unset { key, block_name } => self.process_unset_cmd(key, block_name),
+            Commands::Block { action } => self.process_block_cmd(action),
             _ => Err(Error::CliError(CliErrors::NoOperationFound)),
         }
     }
+    /// Where the document was read from.
+    fn input(&self) -> Result<&Source, Error> {
+        self.cli
+            .input
+            .as_ref()
+            .ok_or(Error::CliError(CliErrors::NoInputFound))
+    }
 }
@@ -0,0 +1,26 @@
+use crate::cli::Position;
+use crate::error::Error;
+use crate::parser::engine::Engine;
+
+impl Engine {
+    pub fn process_move_cmd(mut self, block_name: String, position: Position) -> Result<(), Error> {
+        let from = self.document.movable_index(&block_name)?;
+        // Indexes count the block being moved: taking it out shifts every
+        // block below it up by one.
+        let to = match position {
+            Position::Top => 1,
+            Position::Up => from - 1,
+            Position::Down => from + 1,
+            Position::Before(other) => match self.document.movable_index(&other)? {
+                other if from < other => other - 1,
+                other => other,
+            },
+            Position::After(other) => match self.document.movable_index(&other)? {
+                other if from > other => other + 1,
+                other => other,
+            },
+        };
+        self.document.move_block(&block_name, to)?;
+        self.write_document()
+    }
+}
@@ -0,0 +1,29 @@
+use crate::error::Error;
+use crate::parser::constants::{COMMENT_SYMBOL, KV_DELIMITER};
+use crate::parser::engine::Engine;
+use crate::parser::tokens::quote::Quote;
+
+impl Engine {
+    /// Expanded values are written in the quoting that keeps them literal, so
+    /// reading the output back does not expand them again.
+    pub fn process_resolve_cmd(self, annotate: bool, expand: bool) -> Result<(), Error> {
+        for (block, variable, value) in self.environment(expand)? {
+            let key = &variable.key;
+            let quote = if expand {
+                Quote::fitting(&value)
+            } else {
+                variable.quote
+            };
+            let value = quote.encode(&value);
+            if annotate {
+                println!(
+                    "{key}{KV_DELIMITER}{value} {COMMENT_SYMBOL} {0}",
+                    block.name
+                );
+            } else {
+                println!("{key}{KV_DELIMITER}{value}");
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use crate::error::Error;
+use crate::parser::Span;
+use crate::parser::constants::DEFAULT_BLOCK_NAME;
+use crate::parser::engine::Engine;
+use crate::parser::tokens::quote::Quote;
+use crate::parser::tokens::variable::Variable;
+use crate::parser::validators::validate_variable_name;
+
+impl Engine {
+    /// An existing variable keeps its place, quoting and inline comment; only
+    /// its value changes.
+    pub fn process_set_cmd(
+        mut self,
+        key: String,
+        value: String,
+        block_name: Option<String>,
+    ) -> Result<(), Error> {
+        validate_variable_name(Span::default(), &key)?;
+        let block_name = block_name.as_deref().unwrap_or(DEFAULT_BLOCK_NAME);
+        let mut variable = self
+            .document
+            .get_block_mut(block_name)?
+            .get_variable(&key)
+            .map_or_else(|| Variable::new(&key, ""), Variable::normalized);
+        if variable.quote == Quote::None {
+            variable.quote = Quote::fitting(&value);
+        }
+        variable.value = value;
+        self.document.set_variable(block_name, variable)?;
+        self.write_document()
+    }
+}
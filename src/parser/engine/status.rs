@@ -0,0 +1,58 @@
+use crate::config::Profiles;
+use crate::error::Error;
+use crate::parser::engine::Engine;
+use std::cmp::Reverse;
+
+impl Engine {
+    /// What the current block order sets up: the profile it matches, the
+    /// winner of every group, by `group=` or by name, and the block each key
+    /// comes from. When several profiles match, the one picking the most
+    /// blocks says the most about the order; ties go to the first in the
+    /// config.
+    pub fn process_status_cmd(self) -> Result<(), Error> {
+        let profiles = Profiles::load(self.input()?)?;
+        match profiles
+            .iter()
+            .filter(|(_, block_names)| self.document.is_picked(block_names))
+            .min_by_key(|(_, block_names)| Reverse(block_names.len()))
+        {
+            Some((name, _)) => println!("Profile: {name}"),
+            None => println!("Profile: none"),
+        }
+        let axes = self.document.axes();
+        let groups = self
+            .document
+            .groups()
+            .into_iter()
+            .map(|(group, blocks)| (group.to_string(), blocks))
+            .chain(
+                axes.iter()
+                    .map(|(axis, blocks)| (axis.to_string(), blocks.clone())),
+            )
+            .filter_map(|(group, blocks)| Some((group, blocks.last()?.name.as_str())))
+            .collect::<Vec<_>>();
+        Self::print_section("Groups", &groups);
+        let variables = self
+            .document
+            .resolve()
+            .into_iter()
+            .map(|(key, (block, _))| (key, block.name.as_str()))
+            .collect::<Vec<_>>();
+        Self::print_section("Variables", &variables);
+        Ok(())
+    }
+    fn print_section(title: &str, rows: &[(impl AsRef<str>, &str)]) {
+        if rows.is_empty() {
+            return;
+        }
+        let width = rows
+            .iter()
+            .map(|(name, _)| name.as_ref().len())
+            .max()
+            .unwrap_or(0);
+        println!("\n{title}:");
+        for (name, block_name) in rows {
+            println!("  {:<width$}  {block_name}", name.as_ref());
+        }
+    }
+}
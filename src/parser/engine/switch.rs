@@ -0,0 +1,26 @@
+use crate::error::Error;
+use crate::parser::Span;
+use crate::parser::engine::Engine;
+use crate::parser::validators::validate_block_name;
+
+impl Engine {
+    /// What changed goes to stderr, so piped input still writes only the
+    /// document to stdout.
+    pub fn process_switch_cmd(mut self, environment: String) -> Result<(), Error> {
+        validate_block_name(Span::default(), &environment)?;
+        let switched = self.document.switch(&environment)?;
+        self.write_document()?;
+        let width = switched
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0);
+        for (name, displaced) in switched {
+            match displaced {
+                Some(sibling) => eprintln!("{name:<width$}  replaces {sibling}"),
+                None => eprintln!("{name:<width$}  already active"),
+            }
+        }
+        Ok(())
+    }
+}
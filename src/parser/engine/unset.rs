@@ -0,0 +1,15 @@
+use crate::error::Error;
+use crate::parser::constants::DEFAULT_BLOCK_NAME;
+use crate::parser::engine::Engine;
+
+impl Engine {
+    pub fn process_unset_cmd(
+        mut self,
+        key: String,
+        block_name: Option<String>,
+    ) -> Result<(), Error> {
+        let block_name = block_name.as_deref().unwrap_or(DEFAULT_BLOCK_NAME);
+        self.document.remove_variable(block_name, &key)?;
+        self.write_document()
+    }
+}
@@ -0,0 +1,22 @@
+use crate::config::Profiles;
+use crate::error::{ConfigErrors, Error};
+use crate::parser::engine::Engine;
+
+impl Engine {
+    /// Every block of the profile is checked before anything moves.
+    pub fn process_use_cmd(mut self, profile: String) -> Result<(), Error> {
+        let profiles = Profiles::load(self.input()?)?;
+        let block_names = profiles.get(&profile)?;
+        if let Some(block_name) = block_names
+            .iter()
+            .find(|block_name| self.document.get_index(block_name).is_none())
+        {
+            return Err(Error::ConfigError(ConfigErrors::UnknownBlock(
+                profile,
+                block_name.to_string(),
+            )));
+        }
+        self.document.pick_all(block_names)?;
+        self.write_document()
+    }
+}
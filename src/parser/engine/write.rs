@@ -0,0 +1,21 @@
+use crate::cli::Source;
+use crate::error::{AccessErrors, Error};
+use crate::parser::engine::Engine;
+use std::fs;
+
+impl Engine {
+    /// Writes the document back where it came from: the file it was read
+    /// from, or standard output for piped input.
+    pub fn write_document(&self) -> Result<(), Error> {
+        let Source::FileName(file_path) = self.input()? else {
+            print!("{}", self.document);
+            return Ok(());
+        };
+        fs::write(file_path, self.document.to_string()).map_err(|error| {
+            Error::AccessError(AccessErrors::FileWriteError(
+                file_path.to_string(),
+                error.to_string(),
+            ))
+        })
+    }
+}
@@ -0,0 +1,185 @@
+use crate::error::{Error, ExpansionErrors};
+use crate::parser::expansion::reference::{Fallback, Segment, segments};
+use crate::parser::tokens::Document;
+use crate::parser::tokens::quote::Quote;
+use crate::parser::tokens::variable::Variable;
+use indexmap::IndexMap;
+use std::collections::HashMap;
+use std::env;
+
+/// Expands references in values against the environment the document
+/// resolves to, so a reference follows whichever block wins the key it names.
+/// Keys the file does not set are looked up in the process environment, and
+/// so is a key in its own value, as in `PATH=/opt/bin:$PATH`. Single-quoted
+/// values are literal and never expanded.
+pub struct Expander<'a> {
+    variables: IndexMap<&'a str, &'a Variable>,
+    expanded: HashMap<&'a str, String>,
+    /// Keys being expanded, to catch a value that ends up referring to itself.
+    stack: Vec<&'a str>,
+}
+
+impl<'a> Expander<'a> {
+    pub fn new(document: &'a Document) -> Self {
+        Expander {
+            variables: document
+                .resolve()
+                .into_iter()
+                .map(|(key, (_, variable))| (key, variable))
+                .collect(),
+            expanded: HashMap::new(),
+            stack: Vec::new(),
+        }
+    }
+    /// The expanded value of `key`, empty when it is set nowhere.
+    pub fn value(&mut self, key: &str) -> Result<String, Error> {
+        Ok(self.lookup(key)?.unwrap_or_default())
+    }
+    fn lookup(&mut self, key: &str) -> Result<Option<String>, Error> {
+        let Some((&key, &variable)) = self.variables.get_key_value(key) else {
+            return Ok(env::var(key).ok());
+        };
+        if self.stack.last() == Some(&key) {
+            return Ok(env::var(key).ok());
+        }
+        if let Some(value) = self.expanded.get(key) {
+            return Ok(Some(value.clone()));
+        }
+        if let Some(start) = self.stack.iter().position(|&name| name == key) {
+            let mut chain = self.stack[start..]
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>();
+            chain.push(key.to_string());
+            return Err(Error::ExpansionError(ExpansionErrors::CircularReference(
+                chain,
+            )));
+        }
+        let value = if variable.quote == Quote::Single {
+            variable.value.clone()
+        } else {
+            self.stack.push(key);
+            let value = self.expand(key, &variable.template());
+            self.stack.pop();
+            value?
+        };
+        self.expanded.insert(key, value.clone());
+        Ok(Some(value))
+    }
+    fn expand(&mut self, key: &str, text: &str) -> Result<String, Error> {
+        let segments = segments(text).map_err(|reference| {
+            Error::ExpansionError(ExpansionErrors::InvalidReference(
+                key.to_string(),
+                reference.to_string(),
+            ))
+        })?;
+        let mut out = String::new();
+        for segment in segments {
+            let reference = match segment {
+                Segment::Text(text) => {
+                    out.push_str(text);
+                    continue;
+                }
+                Segment::Reference(reference) => reference,
+            };
+            let value = self
+                .lookup(reference.name)?
+                .filter(|value| !value.is_empty());
+            match (value, reference.fallback) {
+                (Some(value), _) => out.push_str(&value),
+                (None, Fallback::None) => {}
+                (None, Fallback::Default(default)) => out.push_str(&self.expand(key, default)?),
+                (None, Fallback::Required(message)) => {
+                    return Err(Error::ExpansionError(ExpansionErrors::RequiredVariable(
+                        reference.name.to_string(),
+                        message.to_string(),
+                    )));
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn document(source: &str) -> Document {
+        let (document, diagnostics) = Parser::lossless().parse(source);
+        assert!(!diagnostics.has_errors());
+        document
+    }
+
+    #[test]
+    fn follows_the_winning_block() {
+        let mut doc = document(
+            "DB_HOST=localhost\nURL=postgres://${DB_USER:-app}@$DB_HOST/db\n#@ dev\nDB_HOST=dev\n##\n#@ prod\nDB_HOST=prod\n##\n",
+        );
+        assert_eq!(
+            Expander::new(&doc).value("URL").unwrap(),
+            "postgres://app@prod/db"
+        );
+        doc.pick("dev").unwrap();
+        assert_eq!(
+            Expander::new(&doc).value("URL").unwrap(),
+            "postgres://app@dev/db"
+        );
+    }
+
+    #[test]
+    fn single_quotes_are_literal() {
+        let doc = document("A=1\nB='$A'\nC=\"$A\"\n");
+        let mut expander = Expander::new(&doc);
+        assert_eq!(expander.value("B").unwrap(), "$A");
+        assert_eq!(expander.value("C").unwrap(), "1");
+    }
+
+    #[test]
+    fn escaped_dollar() {
+        let doc = document("b=x\nQ=\"a\\$b\"\nR=a\\$b\n");
+        let mut expander = Expander::new(&doc);
+        assert_eq!(expander.value("Q").unwrap(), "a$b");
+        assert_eq!(expander.value("R").unwrap(), "a$b");
+    }
+
+    #[test]
+    fn escaped_backslash_before_a_reference() {
+        let doc = document("b=x\nQ=\"a\\\\$b \\\\\\$b\"\nR=C:\\\\dir\\$b\n");
+        let mut expander = Expander::new(&doc);
+        assert_eq!(expander.value("Q").unwrap(), "a\\x \\$b");
+        assert_eq!(expander.value("R").unwrap(), "C:\\\\dir$b");
+    }
+
+    #[test]
+    fn unset_references() {
+        let doc = document("A=[$ENVMN_TEST_UNSET]\nB=${ENVMN_TEST_UNSET:?needed for B}\n");
+        let mut expander = Expander::new(&doc);
+        assert_eq!(expander.value("A").unwrap(), "[]");
+        assert_eq!(
+            expander.value("B").unwrap_err().to_string(),
+            "Variable 'ENVMN_TEST_UNSET' is not set: needed for B"
+        );
+    }
+
+    #[test]
+    fn self_reference_reads_the_process_environment() {
+        let doc = document("PATH=/opt/bin:$PATH\nENVMN_TEST_UNSET=[${ENVMN_TEST_UNSET:-none}]\n");
+        let mut expander = Expander::new(&doc);
+        assert_eq!(
+            expander.value("PATH").unwrap(),
+            format!("/opt/bin:{}", env::var("PATH").unwrap())
+        );
+        assert_eq!(expander.value("ENVMN_TEST_UNSET").unwrap(), "[none]");
+    }
+
+    #[test]
+    fn circular_references() {
+        let doc = document("A=$B\nB=${C}\nC=$A\n");
+        assert_eq!(
+            Expander::new(&doc).value("B").unwrap_err().to_string(),
+            "Variable 'B' refers to itself: B -> C -> A -> B"
+        );
+    }
+}
@@ -0,0 +1,5 @@
+mod expander;
+mod reference;
+
+pub use expander::Expander;
+pub use reference::{Fallback, Reference, located_references};
@@ -0,0 +1,291 @@
+use crate::parser::constants::KV_DELIMITER;
+use crate::parser::tokens::Span;
+use crate::parser::tokens::quote::Quote;
+use crate::parser::tokens::variable::Variable;
+
+/// A piece of a value: text kept as it is, or a reference to expand.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Segment<'a> {
+    Text(&'a str),
+    Reference(Reference<'a>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Reference<'a> {
+    pub name: &'a str,
+    pub fallback: Fallback<'a>,
+    /// The reference as written, from its `$`.
+    pub text: &'a str,
+}
+
+/// What `${VAR:-default}` and `${VAR:?message}` do when `VAR` is unset or
+/// empty.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fallback<'a> {
+    None,
+    Default(&'a str),
+    Required(&'a str),
+}
+
+/// Splits a variable's template into text and `$VAR`, `${VAR}`,
+/// `${VAR:-default}` and `${VAR:?message}` references. A `$` that starts no
+/// name is kept as text, and `\$` and `\\` stand for a literal `$` and `\`.
+/// On a malformed `${...}` the text from its `$` on is returned as the error.
+pub fn segments(value: &str) -> Result<Vec<Segment<'_>>, &str> {
+    let mut segments = Vec::new();
+    let mut rest = value;
+    while let Some(idx) = rest.find(['$', '\\']) {
+        let text = &rest[..idx];
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+        let tail = &rest[idx + 1..];
+        if rest[idx..].starts_with('\\') {
+            let escaped = usize::from(tail.starts_with(['$', '\\']));
+            segments.push(Segment::Text(&rest[idx + escaped..idx + 1 + escaped]));
+            rest = &tail[escaped..];
+        } else if let Some(body) = tail.strip_prefix('{') {
+            let Some(end) = closing_brace(body) else {
+                return Err(&rest[idx..]);
+            };
+            let text = &rest[idx..idx + end + 3];
+            let reference = braced(&body[..end], text).ok_or(text)?;
+            segments.push(Segment::Reference(reference));
+            rest = &body[end + 1..];
+        } else if tail.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+            let name = &tail[..name_len(tail)];
+            segments.push(Segment::Reference(Reference {
+                name,
+                fallback: Fallback::None,
+                text: &rest[idx..idx + name.len() + 1],
+            }));
+            rest = &tail[name.len()..];
+        } else {
+            segments.push(Segment::Text("$"));
+            rest = tail;
+        }
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest));
+    }
+    Ok(segments)
+}
+
+/// Every reference in `value`, including those in defaults, in the order
+/// they are written. A value with a malformed reference has none.
+pub fn references(value: &str) -> Vec<Reference<'_>> {
+    let mut found = Vec::new();
+    for segment in segments(value).unwrap_or_default() {
+        if let Segment::Reference(reference) = segment {
+            let nested = match reference.fallback {
+                Fallback::Default(default) => references(default),
+                _ => Vec::new(),
+            };
+            found.push(reference);
+            found.extend(nested);
+        }
+    }
+    found
+}
+
+/// The references in a variable's `template` with where each one is written.
+/// A single-quoted value is literal and refers to nothing.
+pub fn located_references<'a>(
+    variable: &Variable,
+    template: &'a str,
+) -> Vec<(Reference<'a>, Span)> {
+    if variable.quote == Quote::Single {
+        return Vec::new();
+    }
+    let source = variable.source();
+    // Escapes in quoted values make the decoded text differ from the source,
+    // so each reference is looked up in the source after the previous one.
+    let mut from = source.find(KV_DELIMITER).map_or(0, |idx| idx + 1);
+    references(template)
+        .into_iter()
+        .map(|reference| {
+            let span = match source[from..].find(reference.text) {
+                Some(idx) => {
+                    from += idx;
+                    let span = variable.span().shifted(&source[..from]);
+                    from += 1;
+                    span
+                }
+                None => variable.span(),
+            };
+            (reference, span)
+        })
+        .collect()
+}
+
+/// Parses what is between `${` and `}`.
+fn braced<'a>(inner: &'a str, text: &'a str) -> Option<Reference<'a>> {
+    let name = &inner[..name_len(inner)];
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let rest = &inner[name.len()..];
+    let fallback = if rest.is_empty() {
+        Fallback::None
+    } else if let Some(default) = rest.strip_prefix(":-") {
+        Fallback::Default(default)
+    } else if let Some(message) = rest.strip_prefix(":?") {
+        Fallback::Required(message)
+    } else {
+        return None;
+    };
+    Some(Reference {
+        name,
+        fallback,
+        text,
+    })
+}
+
+fn name_len(text: &str) -> usize {
+    text.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(text.len())
+}
+
+/// Byte offset of the `}` closing a `${`, skipping over nested `${...}` in
+/// defaults.
+fn closing_brace(body: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (idx, c) in body.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(idx),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(
+        name: &'static str,
+        fallback: Fallback<'static>,
+        text: &'static str,
+    ) -> Segment<'static> {
+        Segment::Reference(Reference {
+            name,
+            fallback,
+            text,
+        })
+    }
+
+    #[test]
+    fn plain_and_braced_references() {
+        assert_eq!(
+            segments("postgres://${DB_USER}@$DB_HOST/db").unwrap(),
+            vec![
+                Segment::Text("postgres://"),
+                reference("DB_USER", Fallback::None, "${DB_USER}"),
+                Segment::Text("@"),
+                reference("DB_HOST", Fallback::None, "$DB_HOST"),
+                Segment::Text("/db"),
+            ]
+        );
+    }
+
+    #[test]
+    fn fallbacks() {
+        assert_eq!(
+            segments("${PORT:-${DEFAULT_PORT}}${HOST:?set HOST}").unwrap(),
+            vec![
+                reference(
+                    "PORT",
+                    Fallback::Default("${DEFAULT_PORT}"),
+                    "${PORT:-${DEFAULT_PORT}}"
+                ),
+                reference("HOST", Fallback::Required("set HOST"), "${HOST:?set HOST}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn lone_dollar_is_text() {
+        assert_eq!(
+            segments("cost: 5$ or $1").unwrap(),
+            vec![
+                Segment::Text("cost: 5"),
+                Segment::Text("$"),
+                Segment::Text(" or "),
+                Segment::Text("$"),
+                Segment::Text("1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn escaped_dollar_is_text() {
+        assert_eq!(
+            segments(r"a\$b \${C} $D").unwrap(),
+            vec![
+                Segment::Text("a"),
+                Segment::Text("$"),
+                Segment::Text("b "),
+                Segment::Text("$"),
+                Segment::Text("{C} "),
+                Segment::Reference(Reference {
+                    name: "D",
+                    fallback: Fallback::None,
+                    text: "$D",
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn escaped_backslash_is_text() {
+        assert_eq!(
+            segments(r"a\\$B c\d").unwrap(),
+            vec![
+                Segment::Text("a"),
+                Segment::Text("\\"),
+                Segment::Reference(Reference {
+                    name: "B",
+                    fallback: Fallback::None,
+                    text: "$B",
+                }),
+                Segment::Text(" c"),
+                Segment::Text("\\"),
+                Segment::Text("d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_references() {
+        assert_eq!(segments("a ${OPEN"), Err("${OPEN"));
+        assert_eq!(segments("${A B} c"), Err("${A B}"));
+        assert_eq!(segments("${}"), Err("${}"));
+    }
+
+    #[test]
+    fn nested_references_in_order() {
+        let names = references("${A:-${B}-$C}$D")
+            .into_iter()
+            .map(|reference| reference.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["A", "B", "C", "D"]);
+    }
+
+    #[test]
+    fn references_point_into_the_source() {
+        let (document, _) =
+            crate::parser::Parser::lossless().parse("X=1\nURL=\"a\\tb\n$X ${X}\"\nRAW='$X'\n");
+        let block = document.get_block("default").unwrap();
+        let url = block.get_variable("URL").unwrap();
+        let spans = located_references(url, &url.template())
+            .into_iter()
+            .map(|(_, span)| span)
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![Span::new(14, 2, 0), Span::new(17, 2, 3)]);
+        let raw = block.get_variable("RAW").unwrap();
+        assert!(located_references(raw, &raw.template()).is_empty());
+    }
+}
@@ -1,8 +1,13 @@
+#[allow(clippy::module_inception)]
 mod parser;
+mod rules;
 mod constants;
 mod tokens;
 mod validators;
 mod engine;
+mod expansion;
 
 pub use parser::Parser;
+pub use rules::Fix;
+pub use tokens::Span;
 pub use engine::Engine;
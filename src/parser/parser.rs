@@ -1,93 +1,396 @@
-use crate::error::{AccessErrors, Error, ParsingErrors};
+use crate::error::{Diagnostics, Error, ParsingErrors};
 use crate::parser::constants;
-use crate::parser::tokens::Block;
-use crate::parser::tokens::Document;
+use crate::parser::rules::Fix;
+use crate::parser::tokens::quote::QuoteError;
 use crate::parser::tokens::variable::Variable;
+use crate::parser::tokens::{Block, Comment, Directive, Document, Span};
 use crate::parser::validators::{validate_block_name, validate_variable_name};
-use std::fs;
-use std::ops::Deref;
 
 pub struct Parser {
     pub document: Document,
+    diagnostics: Diagnostics,
     current_block: Option<Block>,
+    /// Byte offset of every line of the input being parsed.
+    offsets: Vec<usize>,
+    discard_block: bool,
+    lossless: bool,
 }
 
 impl Parser {
     pub fn new() -> Self {
         Parser {
             document: Document::new(),
+            diagnostics: Diagnostics::new(),
             current_block: None,
+            offsets: Vec::new(),
+            discard_block: false,
+            lossless: false,
         }
     }
-    pub fn parse(mut self, input: &str) -> Result<Document, Error> {
+    /// A parser that keeps every byte of the input (blank lines, spacing and
+    /// exact comment text) so the document can be written back untouched.
+    pub fn lossless() -> Self {
+        Parser {
+            document: Document::lossless(),
+            lossless: true,
+            ..Parser::new()
+        }
+    }
+    /// Parses the whole input, carrying on past errors so that every problem
+    /// is reported. A line that fails is skipped and parsing resumes on the
+    /// next one; a block that is still open when another starts is closed.
+    pub fn parse(mut self, input: &str) -> (Document, Diagnostics) {
         if input.is_empty() {
-            return Err(Error::ParsingError(ParsingErrors::EmptyInput));
+            self.diagnostics
+                .push_error(Error::ParsingError(ParsingErrors::EmptyInput(
+                    Span::default(),
+                )));
+            return (self.document, self.diagnostics);
         }
-        let lines = input.lines();
-        for (idx, line) in lines.enumerate() {
-            if line.starts_with(constants::BLOCK_START_SYMBOL) {
-                let name = match self.current_block.as_ref() {
-                    None => line
-                        .trim_start_matches(constants::BLOCK_START_SYMBOL)
-                        .trim(),
-                    Some(Block { name, .. }) => {
-                        return Err(Error::ParsingError(ParsingErrors::NestedBlock(
-                            idx as u16,
-                            name.to_string(),
-                        )));
-                    }
-                };
-                if name == constants::DEFAULT_BLOCK_NAME {
-                    return Err(Error::ParsingError(ParsingErrors::ReservedWord(
-                        idx as u16,
-                        name.to_string(),
-                    )));
+        let sources = input.split_inclusive('\n').collect::<Vec<_>>();
+        let lines = sources
+            .iter()
+            .map(|source| Self::strip_line_break(source))
+            .collect::<Vec<_>>();
+        self.offsets = sources
+            .iter()
+            .scan(0, |offset, source| {
+                let start = *offset;
+                *offset += source.len();
+                Some(start)
+            })
+            .collect();
+        let mut idx = 0;
+        while idx < lines.len() {
+            idx = match self.parse_line(&lines, &sources, idx) {
+                Ok(end) => end,
+                Err(error) => {
+                    self.diagnostics.push_error(error);
+                    idx
                 }
-                validate_block_name(idx as u16, name)?;
-                self.current_block = Some(Block::new(name));
-            } else if line.starts_with(constants::BLOCK_END_SYMBOL) {
-                let block = match self.current_block.take() {
-                    Some(block) => block,
-                    _ => {
-                        return Err(Error::ParsingError(ParsingErrors::BlockNeverOpened(
-                            idx as u16,
-                        )));
-                    }
-                };
-                self.document.add_block(block)?;
-            } else if line.starts_with(constants::COMMENT_SYMBOL) {
-                let comment = line
-                    .trim_start_matches(constants::COMMENT_SYMBOL)
-                    .trim_start();
-                self.get_working_block_mut()?.add_comment(comment);
-            } else if line.trim().len() > 0 {
-                let variable = match line.split_once(constants::KV_DELIMITER) {
-                    Some((key, value)) => Variable::new(key, value),
-                    _ => {
-                        return Err(Error::ParsingError(ParsingErrors::MissingEqSeparator(
-                            idx as u16,
-                        )));
-                    }
-                };
-                validate_variable_name(idx as u16, variable.key.deref())?;
-                self.get_working_block_mut()?.add_variable(variable)?;
-            }
+            } + 1;
         }
-        Ok(self.document)
-    }
-    pub fn parse_file(self, file_path: &str) -> Result<Document, Error> {
-        let content = fs::read_to_string(file_path).map_err(|e| e.to_string());
-        match content {
-            Ok(content) => self.parse(&content),
-            Err(error) => Err(Error::AccessError(AccessErrors::FileError(
-                file_path.to_string(),
-                error,
-            ))),
+        if let Some(block) = self.current_block.take() {
+            self.diagnostics
+                .push_error(Error::ParsingError(ParsingErrors::UnclosedBlock(
+                    block.span(),
+                    block.name.clone(),
+                )));
+            // Kept so that its variables still reach the lint rules
+            if let Err(error) = self.close_block(block) {
+                self.diagnostics.push_error(error);
+            }
         }
+        (self.document, self.diagnostics)
     }
 }
 
 impl Parser {
+    /// Parses the line at `idx` and returns the index of the last line it
+    /// consumed.
+    fn parse_line(&mut self, lines: &[&str], sources: &[&str], idx: usize) -> Result<usize, Error> {
+        let (line, source) = (lines[idx], sources[idx]);
+        let span = self.span(lines, idx, 0);
+        if line.starts_with(constants::BLOCK_START_SYMBOL) {
+            if let Some(block) = self.current_block.take() {
+                self.diagnostics
+                    .push_error(Error::ParsingError(ParsingErrors::NestedBlock(
+                        span,
+                        block.name.clone(),
+                    )));
+                self.close_block(block)?;
+            }
+            let enabled = !line.starts_with(constants::DISABLED_BLOCK_START_SYMBOL);
+            let symbol = match enabled {
+                true => constants::BLOCK_START_SYMBOL,
+                false => constants::DISABLED_BLOCK_START_SYMBOL,
+            };
+            let header = line.trim_start_matches(symbol).trim_start();
+            let name_column = line.len() - header.len();
+            let name_span = self.span(lines, idx, name_column);
+            let header = header.trim_end();
+            let (name, attributes) = header
+                .split_once(char::is_whitespace)
+                .unwrap_or((header, ""));
+            // The block is opened even when its name is rejected, so that its
+            // lines and closing symbol are not mistaken for something else.
+            self.current_block = Some(
+                Block::new(name)
+                    .with_header(source)
+                    .with_span(span)
+                    .with_enabled(enabled),
+            );
+            self.discard_block = true;
+            if name == constants::DEFAULT_BLOCK_NAME {
+                return Err(Error::ParsingError(ParsingErrors::ReservedWord(
+                    name_span,
+                    name.to_string(),
+                )));
+            }
+            validate_block_name(name_span, name)?;
+            self.discard_block = false;
+            let group =
+                self.parse_block_attributes(lines, idx, name_column + name.len(), attributes)?;
+            if let Some(block) = self.current_block.as_mut() {
+                block.group = group;
+            }
+        } else if line.starts_with(constants::BLOCK_END_SYMBOL) {
+            let Some(block) = self.current_block.take() else {
+                return Err(Error::ParsingError(ParsingErrors::BlockNeverOpened(span)));
+            };
+            let text = line
+                .trim_start_matches(constants::BLOCK_END_SYMBOL)
+                .trim_start();
+            if !text.is_empty() {
+                let text_span = self.span(lines, idx, line.len() - text.len());
+                self.diagnostics.push_warning(Error::ParsingError(
+                    ParsingErrors::TextAfterBlockEnd(text_span),
+                ));
+            }
+            self.close_block(block.with_footer(source))?;
+        } else if line.starts_with(constants::COMMENT_SYMBOL) {
+            if self
+                .current_block
+                .as_ref()
+                .is_some_and(|block| !block.enabled())
+                && let Some(end) = self.parse_commented_variable(lines, sources, idx)
+            {
+                return Ok(end);
+            }
+            let comment = line
+                .trim_start_matches(constants::COMMENT_SYMBOL)
+                .trim_start();
+            let block = self.get_working_block_mut()?;
+            match Directive::parse(comment) {
+                Some(directive) => {
+                    block.add_directive(directive.with_source(source).with_span(span))
+                }
+                None => {
+                    block.add_comment(Comment::new(comment).with_source(source).with_span(span))
+                }
+            }
+        } else if line.trim().is_empty() {
+            if self.lossless {
+                self.get_working_block_mut()?.add_blank(source);
+            }
+        } else {
+            let key_span = self.span(lines, idx, line.len() - line.trim_start().len());
+            let Some((key, value)) = line.split_once(constants::KV_DELIMITER) else {
+                return Err(Error::ParsingError(ParsingErrors::MissingEqSeparator(
+                    key_span,
+                )));
+            };
+            let value_column = key.len() + constants::KV_DELIMITER.len();
+            let key = key.trim();
+            let (variable, end, unterminated) =
+                match self.parse_variable(lines, idx, key, value_column) {
+                    Ok((variable, end)) => (variable, end, None),
+                    Err((error, end)) => match Self::close_quote(key, value) {
+                        Some(variable)
+                            if matches!(
+                                error,
+                                Error::ParsingError(ParsingErrors::UnterminatedQuote(_))
+                            ) =>
+                        {
+                            (variable, idx, Some(error))
+                        }
+                        _ => {
+                            self.diagnostics.push_error(error);
+                            return Ok(end);
+                        }
+                    },
+                };
+            let variable = variable.with_span(key_span);
+            if let Err(error) = validate_variable_name(key_span, key) {
+                if let Some(error) = unterminated {
+                    self.diagnostics.push_error(error);
+                }
+                self.diagnostics.push_error(error);
+                return Ok(end);
+            }
+            let source = sources[idx..=end].concat();
+            let block = self.get_working_block_mut()?;
+            let block_name = block.name.clone();
+            let live = !block.enabled();
+            let added = block.add_variable(variable.with_source(&source));
+            if live && added.is_ok() {
+                self.diagnostics.push_warning(Error::ParsingError(
+                    ParsingErrors::LiveVariableInDisabledBlock(
+                        key_span,
+                        key.to_string(),
+                        block_name.clone(),
+                    ),
+                ));
+            }
+            if let Some(error) = unterminated {
+                match added {
+                    Ok(_) => self
+                        .diagnostics
+                        .push_fixable(error, Fix::NormalizeVariable(block_name, key.to_string())),
+                    Err(_) => self.diagnostics.push_error(error),
+                }
+            }
+            added?;
+            return Ok(end);
+        }
+        Ok(idx)
+    }
+    fn close_block(&mut self, block: Block) -> Result<(), Error> {
+        if self.discard_block {
+            self.discard_block = false;
+            return Ok(());
+        }
+        self.document.add_block(block)
+    }
+    /// Reads the `key=value` attributes following a block name, which start
+    /// at byte `column` of line `idx`. Only `group` is known; it names a
+    /// group the way blocks are named.
+    fn parse_block_attributes(
+        &self,
+        lines: &[&str],
+        idx: usize,
+        column: usize,
+        attributes: &str,
+    ) -> Result<Option<String>, Error> {
+        let mut group = None;
+        let mut column = column;
+        for attribute in attributes.split_whitespace() {
+            column += lines[idx][column..].find(attribute).unwrap_or(0);
+            match attribute.split_once(constants::KV_DELIMITER) {
+                Some((constants::GROUP_ATTRIBUTE, value)) => {
+                    let value_column = column + attribute.len() - value.len();
+                    validate_block_name(self.span(lines, idx, value_column), value)?;
+                    group = Some(value.to_string());
+                }
+                _ => {
+                    return Err(Error::ParsingError(ParsingErrors::UnknownBlockAttribute(
+                        self.span(lines, idx, column),
+                        attribute.to_string(),
+                    )));
+                }
+            }
+            column += attribute.len();
+        }
+        Ok(group)
+    }
+    /// Reads a variable whose value starts at byte `column` of line `start`,
+    /// pulling in the following lines while a quoted value is still open.
+    /// Returns the variable, or the error, along with the index of the last
+    /// line to skip past.
+    fn parse_variable(
+        &self,
+        lines: &[&str],
+        start: usize,
+        key: &str,
+        column: usize,
+    ) -> Result<(Variable, usize), (Error, usize)> {
+        let mut raw = lines[start][column..].to_string();
+        let mut end = start;
+        loop {
+            match Variable::parse(key, &raw) {
+                Ok(variable) => return Ok((variable, end)),
+                Err(QuoteError::Unterminated) if end + 1 < lines.len() => {
+                    end += 1;
+                    raw.push('\n');
+                    raw.push_str(lines[end]);
+                }
+                Err(QuoteError::Unterminated) => {
+                    let quote = raw.len() - raw.trim_start().len();
+                    let span = self.value_span(lines, start, column, &raw, quote);
+                    return Err((
+                        Error::ParsingError(ParsingErrors::UnterminatedQuote(span)),
+                        start,
+                    ));
+                }
+                Err(QuoteError::UnexpectedCharacters(position)) => {
+                    let span = self.value_span(lines, start, column, &raw, position);
+                    return Err((
+                        Error::ParsingError(ParsingErrors::UnexpectedCharacters(span)),
+                        end,
+                    ));
+                }
+            }
+        }
+    }
+    /// Reads a variable that disabling its block commented out with `#!`.
+    /// Its source is kept without that prefix, which the block puts back when
+    /// written. Returns the index of the last line it consumed, or `None`
+    /// when the comment is not such a variable.
+    fn parse_commented_variable(
+        &mut self,
+        lines: &[&str],
+        sources: &[&str],
+        idx: usize,
+    ) -> Option<usize> {
+        let prefix = constants::DISABLED_VARIABLE_PREFIX;
+        let line = lines[idx].strip_prefix(prefix)?;
+        let (key, _) = line.split_once(constants::KV_DELIMITER)?;
+        let key_column = prefix.len() + line.len() - line.trim_start().len();
+        let key_span = self.span(lines, idx, key_column);
+        let value_column = key.len() + constants::KV_DELIMITER.len();
+        let key = key.trim();
+        validate_variable_name(key_span, key).ok()?;
+        let uncommented = lines[idx..]
+            .iter()
+            .map_while(|line| line.strip_prefix(prefix))
+            .collect::<Vec<_>>();
+        let (variable, end) = self
+            .parse_variable(&uncommented, 0, key, value_column)
+            .ok()?;
+        let end = idx + end;
+        let source = sources[idx..=end]
+            .iter()
+            .map(|source| source.strip_prefix(prefix).unwrap_or(source))
+            .collect::<String>();
+        let variable = variable
+            .with_span(key_span)
+            .with_source(&source)
+            .with_disabled(true);
+        if let Err(error) = self.current_block.as_mut()?.add_variable(variable) {
+            self.diagnostics.push_error(error);
+        }
+        Some(end)
+    }
+    /// Span of the byte at `column` on line `idx`.
+    fn span(&self, lines: &[&str], idx: usize, column: usize) -> Span {
+        Span::new(
+            self.offsets[idx] + column,
+            idx,
+            lines[idx][..column].chars().count(),
+        )
+    }
+    /// Span of byte `position` of a value that starts at byte `column` of
+    /// line `start` and may continue over the following lines.
+    fn value_span(
+        &self,
+        lines: &[&str],
+        start: usize,
+        column: usize,
+        raw: &str,
+        position: usize,
+    ) -> Span {
+        let before = &raw[..position];
+        match before.rfind('\n') {
+            None => self.span(lines, start, column + position),
+            Some(newline) => self.span(
+                lines,
+                start + before.matches('\n').count(),
+                position - newline - 1,
+            ),
+        }
+    }
+    /// Closes a quote that is never closed at the end of its first line,
+    /// which is almost always what was meant.
+    fn close_quote(key: &str, value: &str) -> Option<Variable> {
+        let quote = value.trim_start().chars().next()?;
+        Variable::parse(key, &format!("{}{quote}", value.trim_end())).ok()
+    }
+    fn strip_line_break(source: &str) -> &str {
+        match source.strip_suffix('\n') {
+            Some(line) => line.strip_suffix('\r').unwrap_or(line),
+            None => source,
+        }
+    }
     fn get_working_block_mut(&mut self) -> Result<&mut Block, Error> {
         match self.current_block.as_mut() {
             Some(block) => Ok(block),
@@ -95,3 +398,95 @@ impl Parser {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_spans() {
+        let input = "# top\nA=1\n#@ dev\n  B = \"é\"\n##\n";
+        let (document, _) = Parser::lossless().parse(input);
+        let default_block = document.get_block(constants::DEFAULT_BLOCK_NAME).unwrap();
+        assert_eq!(
+            default_block.get_variable("A").unwrap().span(),
+            Span::new(6, 1, 0)
+        );
+        let dev = document.get_block("dev").unwrap();
+        assert_eq!(dev.span(), Span::new(10, 2, 0));
+        assert_eq!(dev.get_variable("B").unwrap().span(), Span::new(19, 3, 2));
+    }
+
+    #[test]
+    fn block_attributes() {
+        let input =
+            "#@ prod_database  group=database\n##\n#@ dev group=Db\n##\n#@ test shared\n##\n";
+        let (document, diagnostics) = Parser::lossless().parse(input);
+        assert_eq!(
+            document
+                .get_block("prod_database")
+                .unwrap()
+                .group
+                .as_deref(),
+            Some("database")
+        );
+        assert_eq!(document.get_block("dev").unwrap().group, None);
+        assert_eq!(
+            diagnostics.to_string(),
+            "3:14: error: Block name starts with invalid character 'D'\n5:9: error: Unknown block attribute 'shared'\n"
+        );
+        assert_eq!(document.to_string(), input);
+    }
+
+    #[test]
+    fn disabled_block() {
+        let input = "#@! dev\n# note\n# TODO=rotate\n#! KEY=value\n#! PEM=\"a\n#! b\"\n##\n";
+        let (document, diagnostics) = Parser::lossless().parse(input);
+        assert_eq!(diagnostics.to_string(), "");
+        let dev = document.get_block("dev").unwrap();
+        assert!(!dev.enabled());
+        assert_eq!(
+            dev.variables()
+                .map(|variable| (variable.key.as_str(), variable.value.as_str()))
+                .collect::<Vec<_>>(),
+            [("KEY", "value"), ("PEM", "a\nb")]
+        );
+        assert_eq!(dev.get_variable("KEY").unwrap().span(), Span::new(32, 3, 3));
+        assert_eq!(document.to_string(), input);
+    }
+
+    #[test]
+    fn live_variable_in_disabled_block() {
+        let input = "#@! dev\n#! A=1\nB=2\n##\n";
+        let (document, diagnostics) = Parser::lossless().parse(input);
+        assert_eq!(
+            diagnostics.to_string(),
+            "3:1: warning: Variable 'B' is not commented out, loaders read it although block 'dev' is disabled\n"
+        );
+        let dev = document.get_block("dev").unwrap();
+        assert!(dev.get_variable("A").unwrap().disabled);
+        assert!(!dev.get_variable("B").unwrap().disabled);
+        assert_eq!(document.to_string(), input);
+    }
+
+    #[test]
+    fn error_lines_are_not_limited() {
+        let mut input = "A=1\n".repeat(70_000);
+        input.push_str("NO_SEPARATOR\n");
+        let (_, diagnostics) = Parser::lossless().parse(&input);
+        assert!(
+            diagnostics
+                .to_string()
+                .contains("70001:1: error: Missing key and optional value separator")
+        );
+    }
+
+    #[test]
+    fn multiline_value_errors() {
+        let (_, diagnostics) = Parser::lossless().parse("A=1\nB=\"one\ntwo\" three\n");
+        assert_eq!(
+            diagnostics.to_string(),
+            "3:6: error: Unexpected characters after closing quote\n"
+        );
+    }
+}
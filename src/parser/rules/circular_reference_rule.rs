@@ -0,0 +1,124 @@
+use crate::error::{LintErrors, Severity};
+use crate::parser::expansion::{Reference, located_references};
+use crate::parser::rules::rule::Rule;
+use crate::parser::tokens::{Document, Span};
+use indexmap::IndexMap;
+use std::collections::VecDeque;
+
+/// Values that end up referring to themselves cannot be expanded. Cycles are
+/// looked for in the variables the document resolves to, as expansion does,
+/// and every reference that closes one is reported. A key in its own value
+/// is read from the process environment, so it closes none.
+pub struct CircularReferenceRule;
+
+impl Rule for CircularReferenceRule {
+    fn id(&self) -> &'static str {
+        "circular-reference"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn check(&self, document: &Document) -> Vec<LintErrors> {
+        let resolved = document.resolve();
+        let templates = resolved
+            .iter()
+            .map(|(&key, (_, variable))| (key, variable.template()))
+            .collect::<IndexMap<_, _>>();
+        let references = resolved
+            .iter()
+            .map(|(&key, (_, variable))| (key, located_references(variable, &templates[key])))
+            .collect::<IndexMap<_, _>>();
+        let mut errors = Vec::new();
+        for (&key, (block, _)) in &resolved {
+            for (reference, span) in &references[key] {
+                if reference.name == key {
+                    continue;
+                }
+                if let Some(path) = Self::path(&references, reference.name, key) {
+                    let mut chain = vec![key.to_string()];
+                    chain.extend(path);
+                    errors.push(LintErrors::CircularReference(
+                        *span,
+                        chain,
+                        block.name.clone(),
+                    ));
+                }
+            }
+        }
+        errors
+    }
+}
+
+impl CircularReferenceRule {
+    /// The shortest chain of references leading from `from` to `to`.
+    fn path(
+        references: &IndexMap<&str, Vec<(Reference, Span)>>,
+        from: &str,
+        to: &str,
+    ) -> Option<Vec<String>> {
+        let mut previous: IndexMap<&str, Option<&str>> = IndexMap::from([(from, None)]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(name) = queue.pop_front() {
+            if name == to {
+                let mut path = vec![name.to_string()];
+                let mut current = name;
+                while let Some(Some(before)) = previous.get(current) {
+                    path.push(before.to_string());
+                    current = before;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for (reference, _) in references.get(name).into_iter().flatten() {
+                if !previous.contains_key(reference.name) {
+                    previous.insert(reference.name, Some(name));
+                    queue.push_back(reference.name);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn messages(source: &str) -> Vec<String> {
+        let (document, _) = Parser::lossless().parse(source);
+        CircularReferenceRule
+            .check(&document)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn two_variable_cycle() {
+        assert_eq!(
+            messages("A=${B}\nB=x$A\nC=$A\n"),
+            vec![
+                "Variable 'A' in block 'default' refers to itself: A -> B -> A",
+                "Variable 'B' in block 'default' refers to itself: B -> A -> B",
+            ]
+        );
+    }
+
+    #[test]
+    fn self_reference_is_not_a_cycle() {
+        assert!(messages("A=${A:-x}\nPATH=/opt/bin:$PATH\n").is_empty());
+        assert_eq!(
+            messages("A=$A:$B\nB=$A\n"),
+            vec![
+                "Variable 'A' in block 'default' refers to itself: A -> B -> A",
+                "Variable 'B' in block 'default' refers to itself: B -> A -> B",
+            ]
+        );
+    }
+
+    #[test]
+    fn only_the_winning_definitions_count() {
+        assert!(messages("A=$B\nB=$A\n#@ dev\nB=1\n##\n").is_empty());
+    }
+}
@@ -0,0 +1,62 @@
+use crate::error::{LintErrors, Severity};
+use crate::parser::rules::rule::Rule;
+use crate::parser::tokens::Document;
+
+/// A named block that sets a key to the value the default block already
+/// gives it overrides nothing. Alternative blocks sharing a value (the same
+/// port in `prod_database` and `dev_database`) are expected and not reported.
+pub struct DuplicateValueRule;
+
+impl Rule for DuplicateValueRule {
+    fn id(&self) -> &'static str {
+        "duplicate-value"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn check(&self, document: &Document) -> Vec<LintErrors> {
+        let blocks = document.get_blocks();
+        let Some((default_block, named_blocks)) = blocks.split_first() else {
+            return Vec::new();
+        };
+        named_blocks
+            .iter()
+            .flat_map(|block| {
+                block
+                    .variables()
+                    .filter(|variable| {
+                        default_block
+                            .get_variable(&variable.key)
+                            .is_some_and(|default| default.value == variable.value)
+                    })
+                    .map(|variable| {
+                        LintErrors::DuplicateValue(variable.key.clone(), block.name.clone())
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn same_value_as_default() {
+        let (document, _) =
+            Parser::lossless().parse("DEBUG=true\nPORT=1\n#@ local\nDEBUG='true'\nPORT=2\n##\n");
+        let errors = DuplicateValueRule.check(&document);
+        assert_eq!(errors.len(), 1);
+        assert!(
+            matches!(&errors[0], LintErrors::DuplicateValue(key, block) if key == "DEBUG" && block == "local")
+        );
+    }
+
+    #[test]
+    fn alternatives_may_share_values() {
+        let (document, _) =
+            Parser::lossless().parse("#@ prod\nPORT=5432\n##\n#@ dev\nPORT=5432\n##\n");
+        assert!(DuplicateValueRule.check(&document).is_empty());
+    }
+}
@@ -0,0 +1,65 @@
+use crate::error::{LintErrors, Severity};
+use crate::parser::constants::DEFAULT_BLOCK_NAME;
+use crate::parser::rules::Fix;
+use crate::parser::rules::rule::Rule;
+use crate::parser::tokens::Document;
+
+pub struct EmptyBlockRule;
+
+impl Rule for EmptyBlockRule {
+    fn id(&self) -> &'static str {
+        "empty-block"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn check(&self, document: &Document) -> Vec<LintErrors> {
+        document
+            .get_blocks()
+            .into_iter()
+            .filter(|block| block.name != DEFAULT_BLOCK_NAME)
+            .filter(|block| block.variables().next().is_none())
+            .map(|block| LintErrors::EmptyBlock(block.name.clone()))
+            .collect()
+    }
+    /// Blocks that still hold comments are left for the author to decide.
+    fn fix(&self, document: &Document, error: &LintErrors) -> Option<Fix> {
+        let block_name = error.block_name();
+        document
+            .get_block(block_name)?
+            .lines()
+            .all(|line| line.to_string().is_empty())
+            .then(|| Fix::RemoveBlock(block_name.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn block_with_only_comments() {
+        let (document, _) = Parser::lossless().parse("#@ empty\n# nothing here\n\n##\n");
+        let errors = EmptyBlockRule.check(&document);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(&errors[0], LintErrors::EmptyBlock(name) if name == "empty"));
+    }
+
+    #[test]
+    fn fix_keeps_commented_blocks() {
+        let (document, _) = Parser::lossless().parse("#@ a\n\n##\n#@ b\n# note\n##\n");
+        let errors = EmptyBlockRule.check(&document);
+        assert_eq!(
+            EmptyBlockRule.fix(&document, &errors[0]),
+            Some(Fix::RemoveBlock("a".to_string()))
+        );
+        assert_eq!(EmptyBlockRule.fix(&document, &errors[1]), None);
+    }
+
+    #[test]
+    fn default_block_is_ignored() {
+        let (document, _) = Parser::lossless().parse("# only a comment\n#@ full\nA=1\n##\n");
+        assert!(EmptyBlockRule.check(&document).is_empty());
+    }
+}
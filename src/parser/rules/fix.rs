@@ -0,0 +1,80 @@
+use crate::error::{AccessErrors, Error};
+use crate::parser::tokens::variable::Variable;
+use crate::parser::tokens::{Block, Document};
+
+/// A machine-applicable change that resolves a finding. Variants are ordered
+/// the way they are applied, so a variable is rewritten before it is renamed.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Fix {
+    /// Writes the variable (block, key) back the normalized way, which drops
+    /// stray whitespace and closes its quote.
+    NormalizeVariable(String, String),
+    /// Renames the variable (block, key) to the new key.
+    RenameVariable(String, String, String),
+    RemoveBlock(String),
+}
+
+impl Fix {
+    pub fn apply(&self, document: &mut Document) -> Result<(), Error> {
+        match self {
+            Fix::NormalizeVariable(block_name, key) => {
+                let block = document.get_block_mut(block_name)?;
+                let variable = Self::variable(block, key)?.normalized();
+                block.replace_variable(key, variable)
+            }
+            Fix::RenameVariable(block_name, key, new_key) => {
+                let block = document.get_block_mut(block_name)?;
+                let mut variable = Self::variable(block, key)?.normalized();
+                variable.key = new_key.clone();
+                block.replace_variable(key, variable)
+            }
+            Fix::RemoveBlock(block_name) => document.remove_block(block_name).map(|_| ()),
+        }
+    }
+    fn variable<'a>(block: &'a Block, key: &str) -> Result<&'a Variable, Error> {
+        block.get_variable(key).ok_or_else(|| {
+            Error::AccessError(AccessErrors::VariableNotFound(
+                key.to_string(),
+                block.name.clone(),
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn normalize_variable() {
+        let (mut document, _) = Parser::lossless().parse("# top\nA = 1  \nB=2\n");
+        Fix::NormalizeVariable("default".to_string(), "A".to_string())
+            .apply(&mut document)
+            .unwrap();
+        assert_eq!(document.to_string(), "# top\nA=1\nB=2\n");
+    }
+
+    #[test]
+    fn rename_variable() {
+        let (mut document, _) = Parser::lossless().parse("a=1\nB=2\n");
+        Fix::RenameVariable("default".to_string(), "a".to_string(), "A".to_string())
+            .apply(&mut document)
+            .unwrap();
+        assert_eq!(document.to_string(), "A=1\nB=2\n");
+        assert!(
+            Fix::RenameVariable("default".to_string(), "A".to_string(), "B".to_string())
+                .apply(&mut document)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn remove_block() {
+        let (mut document, _) = Parser::lossless().parse("A=1\n#@ empty\n##\nB=2\n");
+        Fix::RemoveBlock("empty".to_string())
+            .apply(&mut document)
+            .unwrap();
+        assert_eq!(document.to_string(), "A=1\nB=2\n");
+    }
+}
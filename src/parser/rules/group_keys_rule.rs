@@ -0,0 +1,76 @@
+use crate::error::{LintErrors, Severity};
+use crate::parser::rules::rule::Rule;
+use crate::parser::tokens::Document;
+use indexmap::IndexSet;
+
+/// Blocks of a group stand in for each other, so switching between them
+/// should not leave a key behind from the one that was active before.
+pub struct GroupKeysRule;
+
+impl Rule for GroupKeysRule {
+    fn id(&self) -> &'static str {
+        "group-keys"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn check(&self, document: &Document) -> Vec<LintErrors> {
+        let mut errors = Vec::new();
+        for (group, blocks) in document.groups() {
+            let keys = blocks
+                .iter()
+                .flat_map(|block| block.variables().map(|variable| variable.key.as_str()))
+                .collect::<IndexSet<_>>();
+            for block in blocks {
+                let missing = keys
+                    .iter()
+                    .filter(|key| block.get_variable(key).is_none())
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>();
+                if !missing.is_empty() {
+                    errors.push(LintErrors::GroupKeys(
+                        missing,
+                        group.to_string(),
+                        block.name.clone(),
+                    ));
+                }
+            }
+        }
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn messages(source: &str) -> Vec<String> {
+        let (document, _) = Parser::lossless().parse(source);
+        GroupKeysRule
+            .check(&document)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn same_keys() {
+        assert!(
+            messages("#@ prod group=db\nA=1\nB=2\n##\n#@ dev group=db\nB=3\nA=4\n##\n").is_empty()
+        );
+    }
+
+    #[test]
+    fn missing_keys() {
+        assert_eq!(
+            messages(
+                "#@ prod group=db\nA=1\nB=2\n##\n#@ dev group=db\nA=3\nC=4\n##\n#@ other\nA=5\n##\n"
+            ),
+            [
+                "Block 'prod' of group 'db' does not set C, which other blocks of the group do",
+                "Block 'dev' of group 'db' does not set B, which other blocks of the group do",
+            ]
+        );
+    }
+}
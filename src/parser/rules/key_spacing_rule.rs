@@ -0,0 +1,59 @@
+use crate::error::{LintErrors, Severity};
+use crate::parser::constants::KV_DELIMITER;
+use crate::parser::rules::Fix;
+use crate::parser::rules::rule::Rule;
+use crate::parser::tokens::Document;
+
+/// `KEY = value` parses here, but shells and many loaders reject the spaces.
+pub struct KeySpacingRule;
+
+impl Rule for KeySpacingRule {
+    fn id(&self) -> &'static str {
+        "key-spacing"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn check(&self, document: &Document) -> Vec<LintErrors> {
+        document
+            .get_blocks()
+            .into_iter()
+            .flat_map(|block| {
+                block
+                    .variables()
+                    .filter(|variable| {
+                        let source = variable.source();
+                        source.split_once(KV_DELIMITER).is_some_and(|(key, value)| {
+                            key != variable.key || value.starts_with([' ', '\t'])
+                        })
+                    })
+                    .map(|variable| {
+                        LintErrors::KeySpacing(variable.key.clone(), block.name.clone())
+                    })
+            })
+            .collect()
+    }
+    fn fix(&self, _document: &Document, error: &LintErrors) -> Option<Fix> {
+        let key = error.key()?;
+        Some(Fix::NormalizeVariable(
+            error.block_name().to_string(),
+            key.to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn spaces_around_delimiter() {
+        let (document, _) = Parser::lossless().parse("A = 1\nB=2\nC =3\n#@ b\nD=\t4\n##\n");
+        let errors = KeySpacingRule.check(&document);
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(&errors[0], LintErrors::KeySpacing(key, _) if key == "A"));
+        assert!(matches!(&errors[1], LintErrors::KeySpacing(key, _) if key == "C"));
+        assert!(matches!(&errors[2], LintErrors::KeySpacing(key, _) if key == "D"));
+    }
+}
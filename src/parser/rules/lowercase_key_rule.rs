@@ -0,0 +1,74 @@
+use crate::error::{LintErrors, Severity};
+use crate::parser::rules::Fix;
+use crate::parser::rules::rule::Rule;
+use crate::parser::tokens::Document;
+
+pub struct LowercaseKeyRule;
+
+impl Rule for LowercaseKeyRule {
+    fn id(&self) -> &'static str {
+        "lowercase-key"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn check(&self, document: &Document) -> Vec<LintErrors> {
+        document
+            .get_blocks()
+            .into_iter()
+            .flat_map(|block| {
+                block
+                    .variables()
+                    .filter(|variable| variable.key.chars().any(|c| c.is_ascii_lowercase()))
+                    .map(|variable| {
+                        LintErrors::LowercaseKey(variable.key.clone(), block.name.clone())
+                    })
+            })
+            .collect()
+    }
+    /// Renaming is only offered when the uppercase name is still free.
+    fn fix(&self, document: &Document, error: &LintErrors) -> Option<Fix> {
+        let key = error.key()?;
+        let block_name = error.block_name();
+        let block = document.get_block(block_name)?;
+        let new_key = key.to_ascii_uppercase();
+        if block.get_variable(&new_key).is_some() {
+            return None;
+        }
+        Some(Fix::RenameVariable(
+            block_name.to_string(),
+            key.to_string(),
+            new_key,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn mixed_case_keys() {
+        let (document, _) = Parser::lossless().parse("db_host=a\nDbPort=1\nDB_NAME_2=x\n");
+        let errors = LowercaseKeyRule.check(&document);
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(&errors[0], LintErrors::LowercaseKey(key, _) if key == "db_host"));
+        assert!(matches!(&errors[1], LintErrors::LowercaseKey(key, _) if key == "DbPort"));
+    }
+
+    #[test]
+    fn fix_only_when_uppercase_name_is_free() {
+        let (document, _) = Parser::lossless().parse("db_host=a\nDbPort=1\nDBPORT=2\n");
+        let errors = LowercaseKeyRule.check(&document);
+        assert_eq!(
+            LowercaseKeyRule.fix(&document, &errors[0]),
+            Some(Fix::RenameVariable(
+                "default".to_string(),
+                "db_host".to_string(),
+                "DB_HOST".to_string()
+            ))
+        );
+        assert_eq!(LowercaseKeyRule.fix(&document, &errors[1]), None);
+    }
+}
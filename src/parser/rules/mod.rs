@@ -0,0 +1,14 @@
+mod circular_reference_rule;
+mod duplicate_value_rule;
+mod empty_block_rule;
+mod fix;
+mod group_keys_rule;
+mod key_spacing_rule;
+mod lowercase_key_rule;
+mod registry;
+mod rule;
+mod trailing_whitespace_rule;
+mod undefined_reference_rule;
+
+pub use fix::Fix;
+pub use registry::Registry;
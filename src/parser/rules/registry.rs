@@ -0,0 +1,240 @@
+use crate::error::{CliErrors, Diagnostics, Error, LintErrors, Severity};
+use crate::parser::constants::DEFAULT_BLOCK_NAME;
+use crate::parser::rules::circular_reference_rule::CircularReferenceRule;
+use crate::parser::rules::duplicate_value_rule::DuplicateValueRule;
+use crate::parser::rules::empty_block_rule::EmptyBlockRule;
+use crate::parser::rules::group_keys_rule::GroupKeysRule;
+use crate::parser::rules::key_spacing_rule::KeySpacingRule;
+use crate::parser::rules::lowercase_key_rule::LowercaseKeyRule;
+use crate::parser::rules::rule::Rule;
+use crate::parser::rules::trailing_whitespace_rule::TrailingWhitespaceRule;
+use crate::parser::rules::undefined_reference_rule::UndefinedReferenceRule;
+use crate::parser::tokens::directive::Scope;
+use crate::parser::tokens::variable::Variable;
+use crate::parser::tokens::{Document, Span};
+
+/// Reported for suppression directives that silenced nothing. It is not a
+/// rule of its own since it looks at what the other rules found.
+const UNUSED_DIRECTIVE_RULE: &str = "unused-directive";
+
+/// Every built-in lint rule, each with the severity it currently reports at.
+/// A rule without a severity is turned off.
+pub struct Registry {
+    rules: Vec<(Box<dyn Rule>, Option<Severity>)>,
+    unused_directive: Option<Severity>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(EmptyBlockRule),
+            Box::new(DuplicateValueRule),
+            Box::new(TrailingWhitespaceRule),
+            Box::new(LowercaseKeyRule),
+            Box::new(KeySpacingRule),
+            Box::new(UndefinedReferenceRule { process_env: false }),
+            Box::new(CircularReferenceRule),
+            Box::new(GroupKeysRule),
+        ];
+        Registry {
+            rules: rules
+                .into_iter()
+                .map(|rule| {
+                    let severity = Some(rule.default_severity());
+                    (rule, severity)
+                })
+                .collect(),
+            unused_directive: Some(Severity::Warning),
+        }
+    }
+    pub fn set_severity(&mut self, id: &str, severity: Option<Severity>) -> Result<(), Error> {
+        if id == UNUSED_DIRECTIVE_RULE {
+            self.unused_directive = severity;
+            return Ok(());
+        }
+        match self.rules.iter_mut().find(|(rule, _)| rule.id() == id) {
+            Some((_, level)) => {
+                *level = severity;
+                Ok(())
+            }
+            None => Err(Error::CliError(CliErrors::UnknownRule(id.to_string()))),
+        }
+    }
+    /// Counts keys set in the process environment as defined when checking
+    /// references.
+    pub fn with_process_env(mut self) -> Self {
+        let rule: Box<dyn Rule> = Box::new(UndefinedReferenceRule { process_env: true });
+        if let Some(entry) = self
+            .rules
+            .iter_mut()
+            .find(|(existing, _)| existing.id() == rule.id())
+        {
+            entry.0 = rule;
+        }
+        self
+    }
+    /// Runs every enabled rule and reports what the document's directives
+    /// do not suppress, followed by the directives that suppressed nothing.
+    pub fn check(&self, document: &Document, diagnostics: &mut Diagnostics) {
+        let directives = document.directives();
+        let mut used = vec![Vec::new(); directives.len()];
+        for (rule, severity) in &self.rules {
+            let Some(severity) = severity else {
+                continue;
+            };
+            for error in rule.check(document) {
+                let suppressed_by = directives.iter().position(|(block, directive, target)| {
+                    directive.disables(rule.id())
+                        && block.name == error.block_name()
+                        && match directive.scope {
+                            Scope::Block => true,
+                            Scope::NextLine => {
+                                target.is_some_and(|variable| error.key() == Some(&variable.key))
+                            }
+                        }
+                });
+                match suppressed_by {
+                    Some(idx) => used[idx].push(rule.id()),
+                    None => {
+                        let fix = rule.fix(document, &error);
+                        let span = error.span().or_else(|| Self::span(document, &error));
+                        diagnostics.push_lint(rule.id(), *severity, error, span, fix);
+                    }
+                }
+            }
+        }
+        let Some(severity) = self.unused_directive else {
+            return;
+        };
+        for ((block, directive, _), used) in directives.iter().zip(used) {
+            if directive.rules.is_empty() && used.is_empty() {
+                diagnostics.push_lint(
+                    UNUSED_DIRECTIVE_RULE,
+                    severity,
+                    LintErrors::UnusedDirective(block.name.clone(), None),
+                    Some(directive.span()),
+                    None,
+                );
+            }
+            for rule in &directive.rules {
+                if !used.contains(&rule.as_str()) && !self.is_off(rule) {
+                    diagnostics.push_lint(
+                        UNUSED_DIRECTIVE_RULE,
+                        severity,
+                        LintErrors::UnusedDirective(block.name.clone(), Some(rule.clone())),
+                        Some(directive.span()),
+                        None,
+                    );
+                }
+            }
+        }
+    }
+    /// Points a finding at its variable, or at its block when it has none.
+    /// The default block has no header to point at.
+    fn span(document: &Document, error: &LintErrors) -> Option<Span> {
+        let block = document.get_block(error.block_name())?;
+        match error.key() {
+            Some(key) => block.get_variable(key).map(Variable::span),
+            None if block.name != DEFAULT_BLOCK_NAME => Some(block.span()),
+            None => None,
+        }
+    }
+    /// A directive naming a rule that was turned off has nothing to do, but
+    /// it is not wrong either.
+    fn is_off(&self, id: &str) -> bool {
+        self.rules
+            .iter()
+            .any(|(rule, severity)| rule.id() == id && severity.is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn unknown_rule() {
+        let mut registry = Registry::new();
+        assert!(registry.set_severity("no-such-rule", None).is_err());
+    }
+
+    fn check(input: &str) -> Vec<String> {
+        let (document, _) = Parser::lossless().parse(input);
+        let mut diagnostics = Diagnostics::new();
+        Registry::new().check(&document, &mut diagnostics);
+        diagnostics
+            .to_string()
+            .lines()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn next_line_directive() {
+        let input = "A=1\nB=2\n#@ dev\n# envmn-disable-next-line duplicate-value\nA=1\nB=2\n##\n";
+        assert_eq!(
+            check(input),
+            vec![
+                "6:1: warning[duplicate-value]: Variable 'B' in block 'dev' repeats the value from the default block"
+            ]
+        );
+    }
+
+    #[test]
+    fn block_directive() {
+        let input =
+            "#@ legacy\n# envmn-disable\nlower=1\n##\n#@ empty\n# envmn-disable empty-block\n##\n";
+        assert!(check(input).is_empty());
+    }
+
+    #[test]
+    fn unused_directive() {
+        let input = "# envmn-disable-next-line lowercase-key\nA=1\n# envmn-disable\n";
+        assert_eq!(
+            check(input),
+            vec![
+                "1:1: warning[unused-directive]: Directive in block 'default' disables 'lowercase-key' but nothing was reported",
+                "3:1: warning[unused-directive]: Directive in block 'default' suppresses nothing",
+            ]
+        );
+    }
+
+    #[test]
+    fn directive_before_block_targets_nothing() {
+        let input = "A=1\n# envmn-disable-next-line duplicate-value\n#@ dev\nA=1\n##\n";
+        assert_eq!(check(input).len(), 2);
+    }
+
+    #[test]
+    fn directive_for_disabled_rule_is_not_reported() {
+        let (document, _) = Parser::lossless().parse("# envmn-disable lowercase-key\nA=1\n");
+        let mut registry = Registry::new();
+        registry.set_severity("lowercase-key", None).unwrap();
+        let mut diagnostics = Diagnostics::new();
+        registry.check(&document, &mut diagnostics);
+        assert_eq!(diagnostics.max_severity(), None);
+    }
+
+    #[test]
+    fn configured_severity() {
+        let (document, _) = Parser::lossless().parse("#@ empty\n##\n");
+        let mut registry = Registry::new();
+
+        let mut diagnostics = Diagnostics::new();
+        registry.check(&document, &mut diagnostics);
+        assert_eq!(diagnostics.max_severity(), Some(Severity::Warning));
+
+        registry
+            .set_severity("empty-block", Some(Severity::Error))
+            .unwrap();
+        let mut diagnostics = Diagnostics::new();
+        registry.check(&document, &mut diagnostics);
+        assert_eq!(diagnostics.max_severity(), Some(Severity::Error));
+
+        registry.set_severity("empty-block", None).unwrap();
+        let mut diagnostics = Diagnostics::new();
+        registry.check(&document, &mut diagnostics);
+        assert_eq!(diagnostics.max_severity(), None);
+    }
+}
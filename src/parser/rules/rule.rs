@@ -0,0 +1,14 @@
+use crate::error::{LintErrors, Severity};
+use crate::parser::rules::Fix;
+use crate::parser::tokens::Document;
+
+pub trait Rule {
+    /// Stable identifier used on the command line and in reports.
+    fn id(&self) -> &'static str;
+    fn default_severity(&self) -> Severity;
+    fn check(&self, document: &Document) -> Vec<LintErrors>;
+    /// The fix for one of this rule's findings, when there is an obvious one.
+    fn fix(&self, _document: &Document, _error: &LintErrors) -> Option<Fix> {
+        None
+    }
+}
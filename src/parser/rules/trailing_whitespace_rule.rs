@@ -0,0 +1,67 @@
+use crate::error::{LintErrors, Severity};
+use crate::parser::rules::Fix;
+use crate::parser::rules::rule::Rule;
+use crate::parser::tokens::Document;
+use crate::parser::tokens::quote::Quote;
+
+/// Unquoted values lose their trailing whitespace in most loaders but keep it
+/// in others, so it is rarely what the author meant.
+pub struct TrailingWhitespaceRule;
+
+impl Rule for TrailingWhitespaceRule {
+    fn id(&self) -> &'static str {
+        "trailing-whitespace"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn check(&self, document: &Document) -> Vec<LintErrors> {
+        document
+            .get_blocks()
+            .into_iter()
+            .flat_map(|block| {
+                block
+                    .variables()
+                    .filter(|variable| {
+                        variable.quote == Quote::None
+                            && variable.comment.is_none()
+                            && variable
+                                .source()
+                                .trim_end_matches(['\n', '\r'])
+                                .ends_with(char::is_whitespace)
+                    })
+                    .map(|variable| {
+                        LintErrors::TrailingWhitespace(variable.key.clone(), block.name.clone())
+                    })
+            })
+            .collect()
+    }
+    fn fix(&self, _document: &Document, error: &LintErrors) -> Option<Fix> {
+        let key = error.key()?;
+        Some(Fix::NormalizeVariable(
+            error.block_name().to_string(),
+            key.to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn unquoted_value() {
+        let (document, _) = Parser::lossless().parse("A=value  \r\nB=value\n#@ b\nC=1\t\n##");
+        let errors = TrailingWhitespaceRule.check(&document);
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(&errors[0], LintErrors::TrailingWhitespace(key, _) if key == "A"));
+        assert!(matches!(&errors[1], LintErrors::TrailingWhitespace(key, _) if key == "C"));
+    }
+
+    #[test]
+    fn quoted_and_commented_values() {
+        let (document, _) = Parser::lossless().parse("A=\"value  \"\nB=value  # note  \n");
+        assert!(TrailingWhitespaceRule.check(&document).is_empty());
+    }
+}
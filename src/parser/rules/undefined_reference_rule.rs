@@ -0,0 +1,97 @@
+use crate::error::{LintErrors, Severity};
+use crate::parser::expansion::{Fallback, located_references};
+use crate::parser::rules::rule::Rule;
+use crate::parser::tokens::Document;
+use std::env;
+
+/// A reference to a key no block sets expands to nothing, which is rarely
+/// what was meant. Only what loaders read counts: keys set in disabled blocks
+/// do not, and references in commented-out values are not checked.
+/// References with a `:-` default or a `:?` message say what should happen
+/// instead and are left alone. Keys from the process environment only count
+/// when `process_env` is set, since they are not there everywhere the file
+/// is used.
+pub struct UndefinedReferenceRule {
+    pub process_env: bool,
+}
+
+impl Rule for UndefinedReferenceRule {
+    fn id(&self) -> &'static str {
+        "undefined-reference"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn check(&self, document: &Document) -> Vec<LintErrors> {
+        let resolved = document.resolve();
+        let defined = |name: &str| {
+            resolved.contains_key(name) || (self.process_env && env::var_os(name).is_some())
+        };
+        let mut errors = Vec::new();
+        for block in document.get_blocks() {
+            for variable in block.variables().filter(|variable| !variable.disabled) {
+                let template = variable.template();
+                for (reference, span) in located_references(variable, &template) {
+                    if reference.fallback == Fallback::None && !defined(reference.name) {
+                        errors.push(LintErrors::UndefinedReference(
+                            span,
+                            reference.name.to_string(),
+                            variable.key.clone(),
+                            block.name.clone(),
+                        ));
+                    }
+                }
+            }
+        }
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+    use crate::parser::tokens::Span;
+
+    #[test]
+    fn reference_to_missing_key() {
+        let (document, _) = Parser::lossless()
+            .parse("A=${B}\n#@ dev\nB=1\nC=x$D ${E:-e} ${ENVMN_TEST_UNSET:?set it}\n##\n");
+        let errors = UndefinedReferenceRule { process_env: false }.check(&document);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            LintErrors::UndefinedReference(span, reference, key, block)
+                if *span == Span::new(21, 3, 3) && reference == "D" && key == "C" && block == "dev"
+        ));
+    }
+
+    #[test]
+    fn disabled_variables_are_not_defined_nor_checked() {
+        let (document, _) =
+            Parser::lossless().parse("B=${Y}\n#@! dev\n#! Y=1\n#! Z=$MISSING\n##\n");
+        let errors = UndefinedReferenceRule { process_env: false }.check(&document);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            LintErrors::UndefinedReference(_, reference, key, block)
+                if reference == "Y" && key == "B" && block == "default"
+        ));
+    }
+
+    #[test]
+    fn process_environment_counts_when_enabled() {
+        let (document, _) = Parser::lossless().parse("A=$PATH\n");
+        assert_eq!(
+            UndefinedReferenceRule { process_env: false }
+                .check(&document)
+                .len(),
+            1
+        );
+        assert!(
+            UndefinedReferenceRule { process_env: true }
+                .check(&document)
+                .is_empty()
+        );
+    }
+}
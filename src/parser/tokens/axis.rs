@@ -0,0 +1,123 @@
+use std::fmt::{Display, Formatter};
+
+/// Which end of a block name holds the environment: `prod_database` has it
+/// as a prefix, `database_prod` as a suffix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    Prefix,
+    Suffix,
+}
+
+/// The blocks that set up the same thing for different environments, such
+/// as `prod_database` and `dev_database`. Block names only hold lowercase
+/// letters, digits and `_`, so `_` always separates the environment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Axis<'a> {
+    pub side: Side,
+    pub role: &'a str,
+}
+
+impl<'a> Axis<'a> {
+    /// The axis `name` puts `environment` on, if it is one of its blocks.
+    pub fn of(name: &'a str, environment: &str) -> Option<Self> {
+        let prefixed = name
+            .strip_prefix(environment)
+            .and_then(|rest| rest.strip_prefix('_'))
+            .filter(|role| !role.is_empty());
+        if let Some(role) = prefixed {
+            return Some(Axis {
+                side: Side::Prefix,
+                role,
+            });
+        }
+        name.strip_suffix(environment)
+            .and_then(|rest| rest.strip_suffix('_'))
+            .filter(|role| !role.is_empty())
+            .map(|role| Axis {
+                side: Side::Suffix,
+                role,
+            })
+    }
+    /// The axes `name` may be on when the environment is not known: with its
+    /// first word as the environment, and with its last.
+    pub fn candidates(name: &'a str) -> impl Iterator<Item = Self> {
+        let first = name.split('_').next().unwrap_or_default();
+        let last = name.rsplit('_').next().unwrap_or_default();
+        [Self::of(name, first), Self::of(name, last)]
+            .into_iter()
+            .flatten()
+    }
+    /// Whether `name` is a block on this axis, for any environment.
+    pub fn contains(&self, name: &str) -> bool {
+        let environment = match self.side {
+            Side::Prefix => name
+                .strip_suffix(self.role)
+                .and_then(|rest| rest.strip_suffix('_')),
+            Side::Suffix => name
+                .strip_prefix(self.role)
+                .and_then(|rest| rest.strip_prefix('_')),
+        };
+        environment.is_some_and(|environment| !environment.is_empty())
+    }
+}
+
+/// The axis with `*` standing for the environment, e.g. `*_database`.
+impl Display for Axis<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.side {
+            Side::Prefix => write!(f, "*_{}", self.role),
+            Side::Suffix => write!(f, "{}_*", self.role),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_and_suffix() {
+        assert_eq!(
+            Axis::of("prod_database", "prod"),
+            Some(Axis {
+                side: Side::Prefix,
+                role: "database"
+            })
+        );
+        assert_eq!(
+            Axis::of("email_prod", "prod"),
+            Some(Axis {
+                side: Side::Suffix,
+                role: "email"
+            })
+        );
+        assert_eq!(Axis::of("production_database", "prod"), None);
+        assert_eq!(Axis::of("prod", "prod"), None);
+        assert_eq!(Axis::of("prod_", "prod"), None);
+    }
+
+    #[test]
+    fn siblings() {
+        let axis = Axis::of("prod_database", "prod").unwrap();
+        assert!(axis.contains("dev_database"));
+        assert!(axis.contains("prod_database"));
+        assert!(!axis.contains("database_dev"));
+        assert!(!axis.contains("dev_cache"));
+        assert!(!axis.contains("_database"));
+
+        let axis = Axis::of("email_prod", "prod").unwrap();
+        assert!(axis.contains("email_dev"));
+        assert!(!axis.contains("dev_email"));
+    }
+
+    #[test]
+    fn candidates() {
+        assert_eq!(
+            Axis::candidates("prod_main_database")
+                .map(|axis| axis.to_string())
+                .collect::<Vec<_>>(),
+            ["*_main_database", "prod_main_*"]
+        );
+        assert_eq!(Axis::candidates("database").count(), 0);
+    }
+}
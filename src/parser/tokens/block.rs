@@ -1,6 +1,13 @@
-use crate::error::{Error, ParsingErrors};
-use crate::parser::constants::{BLOCK_END_SYMBOL, BLOCK_START_SYMBOL, DEFAULT_BLOCK_NAME};
+use crate::error::{AccessErrors, Error, ParsingErrors};
+use crate::parser::constants::{
+    BLOCK_END_SYMBOL, BLOCK_START_SYMBOL, DEFAULT_BLOCK_NAME, DISABLED_BLOCK_START_SYMBOL,
+    DISABLED_VARIABLE_PREFIX, GROUP_ATTRIBUTE, KV_DELIMITER,
+};
+use crate::parser::tokens::comment::Comment;
+use crate::parser::tokens::directive::Directive;
 use crate::parser::tokens::line::Line;
+use crate::parser::tokens::source::push_source;
+use crate::parser::tokens::span::Span;
 use crate::parser::tokens::variable::Variable;
 use indexmap::IndexSet;
 use std::fmt::{Display, Formatter};
@@ -9,33 +16,235 @@ use std::hash::{Hash, Hasher};
 #[derive(Clone, Debug, Eq)]
 pub struct Block {
     pub name: String,
+    /// Blocks of the same group are alternatives: only one of them is meant
+    /// to win.
+    pub group: Option<String>,
+    /// A disabled block has its variables commented out with `#!`, so that
+    /// no loader sees them whatever their order.
+    enabled: bool,
     lines: IndexSet<Line>,
+    header: Option<String>,
+    footer: Option<String>,
+    span: Span,
 }
 
 impl Block {
     pub fn default() -> Self {
         Block {
             name: DEFAULT_BLOCK_NAME.to_string(),
+            group: None,
+            enabled: true,
             lines: IndexSet::new(),
+            header: None,
+            footer: None,
+            span: Span::default(),
         }
     }
     pub fn new(name: &str) -> Self {
         Block {
             name: name.to_string(),
+            group: None,
+            enabled: true,
             lines: IndexSet::new(),
+            header: None,
+            footer: None,
+            span: Span::default(),
         }
     }
     pub fn add_variable(&mut self, variable: Variable) -> Result<(), Error> {
         if !self.lines.insert(Line::Variable(variable.clone())) {
             return Err(Error::ParsingError(ParsingErrors::DuplicateVariable(
+                variable.span(),
                 variable.key,
                 self.name.clone(),
             )));
         }
         Ok(())
     }
-    pub fn add_comment(&mut self, comment: &str) {
-        self.lines.insert(Line::Comment(comment.to_string()));
+    pub fn add_comment(&mut self, comment: Comment) {
+        self.lines.insert(Line::Comment(comment));
+    }
+    pub fn add_directive(&mut self, directive: Directive) {
+        self.lines.insert(Line::Directive(directive));
+    }
+    pub fn add_blank(&mut self, source: &str) {
+        self.lines.insert(Line::Blank(source.to_string()));
+    }
+    pub fn lines(&self) -> impl Iterator<Item = &Line> {
+        self.lines.iter()
+    }
+    pub fn variables(&self) -> impl Iterator<Item = &Variable> {
+        self.lines.iter().filter_map(|line| match line {
+            Line::Variable(variable) => Some(variable),
+            _ => None,
+        })
+    }
+    pub fn get_variable(&self, key: &str) -> Option<&Variable> {
+        match self.lines.get(&Line::Variable(Variable::new(key, ""))) {
+            Some(Line::Variable(variable)) => Some(variable),
+            _ => None,
+        }
+    }
+    /// Puts `variable` where the variable named `key` is, which may rename it.
+    /// It stays commented out if that variable was.
+    pub fn replace_variable(&mut self, key: &str, variable: Variable) -> Result<(), Error> {
+        let Some((index, Line::Variable(replaced))) =
+            self.lines.get_full(&Line::Variable(Variable::new(key, "")))
+        else {
+            return Err(Error::AccessError(AccessErrors::VariableNotFound(
+                key.to_string(),
+                self.name.clone(),
+            )));
+        };
+        let variable = variable.with_disabled(replaced.disabled);
+        match self.lines.replace_index(index, Line::Variable(variable)) {
+            Ok(_) => Ok(()),
+            Err((_, Line::Variable(variable))) => Err(Error::ParsingError(
+                ParsingErrors::DuplicateVariable(variable.span(), variable.key, self.name.clone()),
+            )),
+            Err(_) => unreachable!("only variables are compared by value"),
+        }
+    }
+    /// Adds `variable` at line `index`, moving the lines from there down. In
+    /// a disabled block it is commented out like the others.
+    pub fn insert_variable(&mut self, index: usize, variable: Variable) -> Result<(), Error> {
+        let variable = variable.with_disabled(!self.enabled);
+        if self.get_variable(&variable.key).is_some() {
+            return Err(Error::ParsingError(ParsingErrors::DuplicateVariable(
+                variable.span(),
+                variable.key,
+                self.name.clone(),
+            )));
+        }
+        self.lines.shift_insert(index, Line::Variable(variable));
+        Ok(())
+    }
+    /// Drops the variable named `key` and returns it with the line it was on.
+    pub fn remove_variable(&mut self, key: &str) -> Result<(usize, Variable), Error> {
+        match self
+            .lines
+            .shift_remove_full(&Line::Variable(Variable::new(key, "")))
+        {
+            Some((index, Line::Variable(variable))) => Ok((index, variable)),
+            _ => Err(Error::AccessError(AccessErrors::VariableNotFound(
+                key.to_string(),
+                self.name.clone(),
+            ))),
+        }
+    }
+    /// The line right after the last variable, where a new one belongs.
+    pub fn after_last_variable(&self) -> Option<usize> {
+        self.lines
+            .iter()
+            .rposition(|line| matches!(line, Line::Variable(_)))
+            .map(|index| index + 1)
+    }
+    pub fn lines_len(&self) -> usize {
+        self.lines.len()
+    }
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+    pub fn span(&self) -> Span {
+        self.span
+    }
+    pub fn with_header(mut self, source: &str) -> Self {
+        self.header = Some(source.to_string());
+        self
+    }
+    pub fn with_footer(mut self, source: &str) -> Self {
+        self.footer = Some(source.to_string());
+        self
+    }
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+    /// Enables or disables the block along with every variable in it. The
+    /// header keeps its spacing and trailing comment, only its start symbol
+    /// changes.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.lines = std::mem::take(&mut self.lines)
+            .into_iter()
+            .map(|line| match line {
+                Line::Variable(variable) => Line::Variable(variable.with_disabled(!enabled)),
+                line => line,
+            })
+            .collect();
+        if self.enabled == enabled {
+            return;
+        }
+        let (from, to) = match enabled {
+            true => (DISABLED_BLOCK_START_SYMBOL, BLOCK_START_SYMBOL),
+            false => (BLOCK_START_SYMBOL, DISABLED_BLOCK_START_SYMBOL),
+        };
+        self.header = self
+            .header
+            .as_ref()
+            .map(|header| header.replacen(from, to, 1));
+        self.enabled = enabled;
+    }
+    /// The same block under another name. The header keeps its spacing and
+    /// trailing comment, only the name in it changes.
+    pub fn renamed(&self, name: &str) -> Self {
+        Block {
+            name: name.to_string(),
+            header: self
+                .header
+                .as_ref()
+                .map(|header| header.replacen(&self.name, name, 1)),
+            ..self.clone()
+        }
+    }
+    /// The normalized header line, attributes included.
+    fn header(&self) -> String {
+        let symbol = match self.enabled {
+            true => BLOCK_START_SYMBOL,
+            false => DISABLED_BLOCK_START_SYMBOL,
+        };
+        match &self.group {
+            Some(group) => format!(
+                "{symbol} {0} {GROUP_ATTRIBUTE}{KV_DELIMITER}{group}",
+                self.name
+            ),
+            None => format!("{symbol} {0}", self.name),
+        }
+    }
+    /// A line as written in this block: disabled variables are commented
+    /// out, every line of a multiline value included.
+    fn written(&self, line: &Line, text: String) -> String {
+        match line {
+            Line::Variable(variable) if variable.disabled => text
+                .split_inclusive('\n')
+                .map(|text| format!("{DISABLED_VARIABLE_PREFIX}{text}"))
+                .collect(),
+            _ => text,
+        }
+    }
+    /// The block's text exactly as it was parsed. Lines added since then are
+    /// rendered the normalized way.
+    pub fn source(&self) -> String {
+        let mut out = String::new();
+        if self.name != DEFAULT_BLOCK_NAME {
+            match &self.header {
+                Some(header) => push_source(&mut out, header),
+                None => push_source(&mut out, &format!("{}\n", self.header())),
+            }
+        }
+        for line in &self.lines {
+            push_source(&mut out, &self.written(line, line.source()));
+        }
+        if self.name != DEFAULT_BLOCK_NAME {
+            match &self.footer {
+                Some(footer) => push_source(&mut out, footer),
+                None => push_source(&mut out, &format!("{BLOCK_END_SYMBOL}\n")),
+            }
+        }
+        out
     }
 }
 
@@ -47,20 +256,19 @@ impl Display for Block {
                 "{}",
                 self.lines
                     .iter()
-                    .map(ToString::to_string)
+                    .map(|line| self.written(line, line.to_string()))
                     .collect::<Vec<_>>()
                     .join("\n")
             )
         } else {
             write!(
                 f,
-                "{0} {2}\n{3}\n{1}",
-                BLOCK_START_SYMBOL,
+                "{0}\n{2}\n{1}",
+                self.header(),
                 BLOCK_END_SYMBOL,
-                self.name,
                 self.lines
                     .iter()
-                    .map(ToString::to_string)
+                    .map(|line| self.written(line, line.to_string()))
                     .collect::<Vec<_>>()
                     .join("\n")
             )
@@ -88,7 +296,12 @@ mod tests {
     fn raw_and_new_interop() {
         let v1 = Block {
             name: DEFAULT_BLOCK_NAME.to_string(),
+            group: None,
+            enabled: true,
             lines: IndexSet::new(),
+            header: None,
+            footer: None,
+            span: Span::default(),
         };
         let v2 = Block::new(DEFAULT_BLOCK_NAME);
         let v3 = Block::default();
@@ -108,10 +321,20 @@ mod tests {
             assert!(matches!(block.lines.first().unwrap(), Line::Variable(_)));
         }
 
+        #[test]
+        fn get_variable() {
+            let mut block = Block::new("test");
+            block.add_comment(Comment::new("KEY=value"));
+            block.add_variable(Variable::new("KEY", "value")).unwrap();
+            assert_eq!(block.get_variable("KEY").unwrap().value, "value");
+            assert!(block.get_variable("OTHER").is_none());
+            assert_eq!(block.variables().count(), 1);
+        }
+
         #[test]
         fn add_comment() {
             let mut block = Block::new("test");
-            block.add_comment("test comment");
+            block.add_comment(Comment::new("test comment"));
             assert_eq!(block.lines.len(), 1);
             assert!(matches!(block.lines.first().unwrap(), Line::Comment(_)));
         }
@@ -119,8 +342,8 @@ mod tests {
         #[test]
         fn add_same_comment() {
             let mut block = Block::new("test");
-            block.add_comment("test comment");
-            block.add_comment("test comment");
+            block.add_comment(Comment::new("test comment"));
+            block.add_comment(Comment::new("test comment"));
             assert_eq!(block.lines.len(), 2);
         }
 
@@ -131,6 +354,84 @@ mod tests {
             block.add_variable(Variable::new("KEY", "value")).unwrap();
             block.add_variable(Variable::new("KEY", "value")).unwrap();
         }
+
+        #[test]
+        fn insert_and_remove_variable() {
+            let mut block = Block::new("test");
+            assert_eq!(block.after_last_variable(), None);
+            block.add_variable(Variable::new("A", "1")).unwrap();
+            block.add_comment(Comment::new("end"));
+            assert_eq!(block.after_last_variable(), Some(1));
+            block.insert_variable(1, Variable::new("B", "2")).unwrap();
+            assert!(block.insert_variable(0, Variable::new("A", "3")).is_err());
+            assert_eq!(block.to_string(), "#@ test\nA=1\nB=2\n# end\n##");
+
+            let (index, variable) = block.remove_variable("A").unwrap();
+            assert_eq!((index, variable.value.as_str()), (0, "1"));
+            assert!(block.remove_variable("A").is_err());
+            assert_eq!(block.to_string(), "#@ test\nB=2\n# end\n##");
+        }
+    }
+
+    #[cfg(test)]
+    mod source {
+        use super::*;
+
+        #[test]
+        fn parsed_block_is_exact() {
+            let mut block = Block::new("test").with_header("#@  test \n");
+            block.add_blank("   \n");
+            block.add_comment(Comment::new("note").with_source("#note\n"));
+            block
+                .add_variable(Variable::new("KEY", "value").with_source("KEY=value"))
+                .unwrap();
+            let block = block.with_footer("##\n");
+            assert_eq!(block.source(), "#@  test \n   \n#note\nKEY=value\n##\n");
+        }
+
+        #[test]
+        fn new_block_is_normalized() {
+            let mut block = Block::new("test");
+            block.add_variable(Variable::new("KEY", "value")).unwrap();
+            assert_eq!(block.source(), "#@ test\nKEY=value\n##\n");
+        }
+
+        #[test]
+        fn renamed_block_keeps_header_spacing() {
+            let block = Block::new("test")
+                .with_header("#@  test \n")
+                .with_footer("##\n");
+            let renamed = block.renamed("other");
+            assert_eq!(renamed.name, "other");
+            assert_eq!(renamed.source(), "#@  other \n##\n");
+        }
+
+        #[test]
+        fn disabled_block_comments_variables_out() {
+            let mut block = Block::new("test")
+                .with_header("#@  test \n")
+                .with_footer("##\n");
+            block.add_comment(Comment::new("note").with_source("# note\n"));
+            block
+                .add_variable(Variable::new("KEY", "a\nb").with_source("KEY='a\nb'\n"))
+                .unwrap();
+            block.set_enabled(false);
+            assert_eq!(block.source(), "#@!  test \n# note\n#! KEY='a\n#! b'\n##\n");
+            block.set_enabled(false);
+            block.set_enabled(true);
+            assert_eq!(block.source(), "#@  test \n# note\nKEY='a\nb'\n##\n");
+        }
+
+        #[test]
+        fn variables_added_to_disabled_block_are_commented_out() {
+            let mut block = Block::new("test").with_enabled(false);
+            block.add_variable(Variable::new("LIVE", "1")).unwrap();
+            block.insert_variable(1, Variable::new("NEW", "2")).unwrap();
+            block
+                .replace_variable("LIVE", Variable::new("LIVE", "3"))
+                .unwrap();
+            assert_eq!(block.source(), "#@! test\nLIVE=3\n#! NEW=2\n##\n");
+        }
     }
 
     #[cfg(test)]
@@ -152,6 +453,16 @@ mod tests {
             );
         }
 
+        #[test]
+        fn grouped_block() {
+            let mut block = Block::new("prod_database");
+            block.group = Some("database".to_string());
+            assert_eq!(
+                block.to_string(),
+                format!("{BLOCK_START_SYMBOL} prod_database group=database\n\n{BLOCK_END_SYMBOL}")
+            );
+        }
+
         #[test]
         fn block_with_variables() {
             let variable = Variable::new("KEY", "value");
@@ -161,15 +472,12 @@ mod tests {
 
             default_block.add_variable(variable.clone()).unwrap();
             named_block.add_variable(variable.clone()).unwrap();
-            assert_eq!(
-                default_block.to_string(),
-                format!("{0}", variable.to_string())
-            );
+            assert_eq!(default_block.to_string(), variable.to_string());
             assert_eq!(
                 named_block.to_string(),
                 format!(
                     "{BLOCK_START_SYMBOL} test\n{0}\n{BLOCK_END_SYMBOL}",
-                    variable.to_string()
+                    variable
                 )
             );
 
@@ -177,14 +485,13 @@ mod tests {
             named_block.add_variable(variable2.clone()).unwrap();
             assert_eq!(
                 default_block.to_string(),
-                format!("{0}\n{1}", variable.to_string(), variable2.to_string())
+                format!("{0}\n{1}", variable, variable2)
             );
             assert_eq!(
                 named_block.to_string(),
                 format!(
                     "{BLOCK_START_SYMBOL} test\n{0}\n{1}\n{BLOCK_END_SYMBOL}",
-                    variable.to_string(),
-                    variable2.to_string()
+                    variable, variable2
                 )
             );
         }
@@ -192,8 +499,8 @@ mod tests {
         #[test]
         fn block_with_comments() {
             let mut block = Block::new("test");
-            block.add_comment("test comment");
-            block.add_comment("test comment");
+            block.add_comment(Comment::new("test comment"));
+            block.add_comment(Comment::new("test comment"));
             assert_eq!(
                 block.to_string(),
                 format!(
@@ -207,12 +514,12 @@ mod tests {
             let variable = Variable::new("KEY", "value");
             let mut block = Block::new("test");
             block.add_variable(variable.clone()).unwrap();
-            block.add_comment("test comment");
+            block.add_comment(Comment::new("test comment"));
             assert_eq!(
                 block.to_string(),
                 format!(
                     "{BLOCK_START_SYMBOL} test\n{0}\n# test comment\n{BLOCK_END_SYMBOL}",
-                    variable.to_string()
+                    variable
                 )
             );
         }
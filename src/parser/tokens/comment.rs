@@ -0,0 +1,60 @@
+use crate::parser::constants::COMMENT_SYMBOL;
+use crate::parser::tokens::span::Span;
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Comment {
+    pub text: String,
+    source: Option<String>,
+    span: Span,
+}
+
+impl Comment {
+    pub fn new(text: &str) -> Self {
+        Comment {
+            text: text.to_string(),
+            source: None,
+            span: Span::default(),
+        }
+    }
+    pub fn with_source(mut self, source: &str) -> Self {
+        self.source = Some(source.to_string());
+        self
+    }
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+    pub fn source(&self) -> String {
+        match &self.source {
+            Some(source) => source.clone(),
+            None => format!("{self}\n"),
+        }
+    }
+}
+
+impl Display for Comment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{COMMENT_SYMBOL} {0}", self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_is_normalized() {
+        let comment = Comment::new("note").with_source("#    note  \n");
+        assert_eq!(comment.to_string(), "# note");
+    }
+
+    #[test]
+    fn source_is_exact() {
+        assert_eq!(
+            Comment::new("note").with_source("#    note  \n").source(),
+            "#    note  \n"
+        );
+        assert_eq!(Comment::new("note").source(), "# note\n");
+    }
+}
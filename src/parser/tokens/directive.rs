@@ -0,0 +1,123 @@
+use crate::parser::constants::{COMMENT_SYMBOL, DISABLE_DIRECTIVE, DISABLE_NEXT_LINE_DIRECTIVE};
+use crate::parser::tokens::span::Span;
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Scope {
+    NextLine,
+    Block,
+}
+
+/// A comment that silences lint rules, either for the line right after it
+/// (`# envmn-disable-next-line`) or for the whole block it sits in
+/// (`# envmn-disable`). Listing no rules silences all of them.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Directive {
+    pub scope: Scope,
+    pub rules: Vec<String>,
+    source: Option<String>,
+    span: Span,
+}
+
+impl Directive {
+    pub fn new(scope: Scope, rules: &[&str]) -> Self {
+        Directive {
+            scope,
+            rules: rules.iter().map(ToString::to_string).collect(),
+            source: None,
+            span: Span::default(),
+        }
+    }
+    /// Reads a directive out of a comment's text, that is everything after
+    /// the `#`. Plain comments give `None`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let (scope, rules) = if let Some(rules) = text.strip_prefix(DISABLE_NEXT_LINE_DIRECTIVE) {
+            (Scope::NextLine, rules)
+        } else if let Some(rules) = text.strip_prefix(DISABLE_DIRECTIVE) {
+            (Scope::Block, rules)
+        } else {
+            return None;
+        };
+        if !rules.is_empty() && !rules.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let rules = rules
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|rule| !rule.is_empty())
+            .collect::<Vec<_>>();
+        Some(Directive::new(scope, &rules))
+    }
+    pub fn with_source(mut self, source: &str) -> Self {
+        self.source = Some(source.to_string());
+        self
+    }
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+    pub fn span(&self) -> Span {
+        self.span
+    }
+    pub fn source(&self) -> String {
+        match &self.source {
+            Some(source) => source.clone(),
+            None => format!("{self}\n"),
+        }
+    }
+    pub fn disables(&self, rule: &str) -> bool {
+        self.rules.is_empty() || self.rules.iter().any(|r| r == rule)
+    }
+}
+
+impl Display for Directive {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self.scope {
+            Scope::NextLine => DISABLE_NEXT_LINE_DIRECTIVE,
+            Scope::Block => DISABLE_DIRECTIVE,
+        };
+        write!(f, "{COMMENT_SYMBOL} {name}")?;
+        if !self.rules.is_empty() {
+            write!(f, " {}", self.rules.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_next_line() {
+        assert_eq!(
+            Directive::parse(" envmn-disable-next-line duplicate-value, lowercase-key"),
+            Some(Directive::new(
+                Scope::NextLine,
+                &["duplicate-value", "lowercase-key"]
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_block_without_rules() {
+        let directive = Directive::parse("envmn-disable").unwrap();
+        assert_eq!(directive.scope, Scope::Block);
+        assert!(directive.disables("empty-block"));
+    }
+
+    #[test]
+    fn plain_comments_are_not_directives() {
+        assert_eq!(Directive::parse(" just a note"), None);
+        assert_eq!(Directive::parse("envmn-disabled"), None);
+    }
+
+    #[test]
+    fn display_is_normalized() {
+        let directive = Directive::parse("envmn-disable-next-line  a,b")
+            .unwrap()
+            .with_source("#envmn-disable-next-line  a,b\n");
+        assert_eq!(directive.to_string(), "# envmn-disable-next-line a, b");
+        assert_eq!(directive.source(), "#envmn-disable-next-line  a,b\n");
+    }
+}
@@ -1,39 +1,56 @@
 use crate::error::{AccessErrors, Error, ParsingErrors};
 use crate::parser::constants::DEFAULT_BLOCK_NAME;
+use crate::parser::tokens::axis::Axis;
 use crate::parser::tokens::block::Block;
-use indexmap::IndexSet;
+use crate::parser::tokens::directive::{Directive, Scope};
+use crate::parser::tokens::line::Line;
+use crate::parser::tokens::source::push_source;
+use crate::parser::tokens::variable::Variable;
+use indexmap::{IndexMap, IndexSet};
 use indexmap::set::MutableValues;
+use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Document {
     blocks: IndexSet<Block>,
+    /// Only kept by lossless documents: for every named block position in the
+    /// source, how many lines of the default block came before it.
+    slots: Option<Vec<usize>>,
 }
 
 impl Document {
     pub fn new() -> Self {
         Document {
             blocks: IndexSet::from([Block::default()]),
+            slots: None,
+        }
+    }
+    /// A document that renders back to its source byte for byte. Reordering
+    /// blocks only moves their own text; everything between them stays put.
+    pub fn lossless() -> Self {
+        Document {
+            slots: Some(Vec::new()),
+            ..Document::new()
         }
     }
     pub fn add_block(&mut self, block: Block) -> Result<(), Error> {
         if !self.blocks.insert(block.clone()) {
             return Err(Error::ParsingError(ParsingErrors::DuplicateBlock(
+                block.span(),
                 block.name,
             )));
         }
+        if let Some(slots) = self.slots.as_mut() {
+            slots.push(self.blocks[0].lines_len());
+        }
         Ok(())
     }
     pub fn get_index(&self, name: &str) -> Option<usize> {
-        match self
-            .blocks
-            .get_index_of(&Block::new(name))
-            .ok_or(Error::AccessError(AccessErrors::BlockNotFound(
-                name.to_string(),
-            ))) {
-            Ok(index) => Some(index),
-            Err(_) => None,
-        }
+        self.blocks.get_index_of(&Block::new(name))
+    }
+    pub fn get_block(&self, name: &str) -> Option<&Block> {
+        self.blocks.get(&Block::new(name))
     }
     pub fn get_blocks(&self) -> Vec<&Block> {
         self.blocks.iter().collect::<Vec<_>>()
@@ -41,9 +58,45 @@ impl Document {
     pub fn blocks_len(&self) -> usize {
         self.blocks.len()
     }
+    /// Every suppression directive with the block it sits in and, for
+    /// next-line directives, the variable written right below it. A directive
+    /// at the end of the default block that is followed by a named block
+    /// targets nothing.
+    pub fn directives(&self) -> Vec<(&Block, &Directive, Option<&Variable>)> {
+        let mut directives = Vec::new();
+        for block in &self.blocks {
+            let lines = block.lines().collect::<Vec<_>>();
+            for (idx, line) in lines.iter().enumerate() {
+                let Line::Directive(directive) = line else {
+                    continue;
+                };
+                let interrupted = block.name == DEFAULT_BLOCK_NAME
+                    && self
+                        .slots
+                        .as_ref()
+                        .is_some_and(|slots| slots.contains(&(idx + 1)));
+                let target = match (directive.scope, lines.get(idx + 1)) {
+                    (Scope::NextLine, Some(Line::Variable(variable))) if !interrupted => {
+                        Some(variable)
+                    }
+                    _ => None,
+                };
+                directives.push((block, directive, target));
+            }
+        }
+        directives
+    }
 }
 
 impl Document {
+    pub fn get_block_mut(&mut self, name: &str) -> Result<&mut Block, Error> {
+        match self.get_index(name) {
+            Some(index) => Ok(self.blocks.get_index_mut2(index).unwrap()),
+            None => Err(Error::AccessError(AccessErrors::BlockNotFound(
+                name.to_string(),
+            ))),
+        }
+    }
     pub fn get_default_block_mut(&mut self) -> Result<&mut Block, Error> {
         match self.blocks.get_index_mut2(0) {
             Some(default_block) => Ok(default_block),
@@ -55,6 +108,76 @@ impl Document {
 }
 
 impl Document {
+    /// Where a named block sits; the default block is always first and never
+    /// moves.
+    pub fn movable_index(&self, name: &str) -> Result<usize, Error> {
+        if name == DEFAULT_BLOCK_NAME {
+            return Err(Error::AccessError(AccessErrors::DefaultBlockNotMovable));
+        }
+        self.get_index(name)
+            .ok_or_else(|| Error::AccessError(AccessErrors::BlockNotFound(name.to_string())))
+    }
+    /// Moves a named block to `index`, kept between the default block and
+    /// the end. Like `pick`, only the block's own text moves.
+    pub fn move_block(&mut self, name: &str, index: usize) -> Result<(), Error> {
+        let from = self.movable_index(name)?;
+        self.blocks
+            .move_index(from, index.clamp(1, self.blocks.len() - 1));
+        Ok(())
+    }
+    /// Picks every block in turn, so the last name ends up last. All names
+    /// are checked first: on error the document is left as it was.
+    pub fn pick_all(&mut self, names: &[String]) -> Result<(), Error> {
+        for name in names {
+            self.movable_index(name)?;
+        }
+        for name in names {
+            self.pick(name)?;
+        }
+        Ok(())
+    }
+    /// Whether picking `names` would leave the blocks where they are, as it
+    /// does right after they were picked. Picking nothing never counts.
+    pub fn is_picked(&self, names: &[String]) -> bool {
+        let mut picked = self.clone();
+        !names.is_empty()
+            && picked.pick_all(names).is_ok()
+            && picked
+                .blocks
+                .iter()
+                .zip(&self.blocks)
+                .all(|(block, current)| block.name == current.name)
+    }
+    /// Picks every block of `environment` that has a sibling on its axis,
+    /// keeping their order. Each comes with the sibling that won before,
+    /// unless it already did.
+    pub fn switch(&mut self, environment: &str) -> Result<Vec<(String, Option<String>)>, Error> {
+        let switched = self
+            .blocks
+            .iter()
+            .skip(1)
+            .filter_map(|block| {
+                let siblings = self.siblings(&Axis::of(&block.name, environment)?)?;
+                siblings.iter().find(|other| other.name == block.name)?;
+                let winner = siblings.last()?;
+                let displaced = (winner.name != block.name).then(|| winner.name.clone());
+                Some((block.name.clone(), displaced))
+            })
+            .collect::<Vec<_>>();
+        if switched.is_empty() {
+            return Err(Error::AccessError(AccessErrors::EnvironmentNotFound(
+                environment.to_string(),
+            )));
+        }
+        let names = switched
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        self.pick_all(&names)?;
+        Ok(switched)
+    }
+    /// Moves a block down so that it wins: right below the last other block
+    /// of its group, or to the bottom when it has no group.
     pub fn pick(&mut self, name: &str) -> Result<&Self, Error> {
         if name == DEFAULT_BLOCK_NAME {
             return Err(Error::AccessError(AccessErrors::DefaultBlockNotMovable));
@@ -64,15 +187,287 @@ impl Document {
                 name.to_string(),
             ))),
             Some(index) => {
-                self.blocks.move_index(index, self.blocks.len() - 1);
+                let group = self.blocks[index].group.as_deref();
+                let sibling = group.and_then(|group| {
+                    self.blocks.iter().rposition(|block| {
+                        block.name != name && block.group.as_deref() == Some(group)
+                    })
+                });
+                let target = match sibling {
+                    Some(sibling) => sibling.max(index),
+                    None => self.blocks.len() - 1,
+                };
+                self.blocks.move_index(index, target);
                 Ok(self)
             }
         }
     }
+    /// Enables or disables a named block without moving it.
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) -> Result<(), Error> {
+        if name == DEFAULT_BLOCK_NAME {
+            return Err(Error::AccessError(AccessErrors::DefaultBlockAlwaysEnabled));
+        }
+        self.get_block_mut(name)?.set_enabled(enabled);
+        Ok(())
+    }
+    /// The enabled blocks of every group, in the order they apply. The last
+    /// block of a group is the one that wins.
+    pub fn groups(&self) -> IndexMap<&str, Vec<&Block>> {
+        let mut groups: IndexMap<&str, Vec<&Block>> = IndexMap::new();
+        for block in self.blocks.iter().filter(|block| block.enabled()) {
+            if let Some(group) = &block.group {
+                groups.entry(group.as_str()).or_default().push(block);
+            }
+        }
+        groups
+    }
+    /// Blocks that are alternatives by name, such as `prod_database` and
+    /// `dev_database`, for every axis a block without a `group=` is on, in the
+    /// order they apply. Grouped blocks are left to `groups`.
+    pub fn axes(&self) -> IndexMap<Axis<'_>, Vec<&Block>> {
+        let ungrouped = self
+            .blocks
+            .iter()
+            .skip(1)
+            .filter(|block| block.enabled() && block.group.is_none());
+        let mut axes = IndexMap::new();
+        for axis in ungrouped.flat_map(|block| Axis::candidates(&block.name)) {
+            if !axes.contains_key(&axis)
+                && let Some(siblings) = self.siblings(&axis)
+            {
+                axes.insert(axis, siblings);
+            }
+        }
+        axes
+    }
+    /// The enabled blocks on `axis`, in the order they apply, when they are
+    /// siblings: some key is set by two of them. A name alone is not enough,
+    /// as `prod_cache` and `prod_database` are on the `prod_*` axis too.
+    fn siblings(&self, axis: &Axis) -> Option<Vec<&Block>> {
+        let siblings = self
+            .blocks
+            .iter()
+            .skip(1)
+            .filter(|block| block.enabled() && axis.contains(&block.name))
+            .collect::<Vec<_>>();
+        let mut keys = HashSet::new();
+        siblings
+            .iter()
+            .flat_map(|block| {
+                block
+                    .variables()
+                    .map(|variable| variable.key.as_str())
+                    .collect::<HashSet<_>>()
+            })
+            .any(|key| !keys.insert(key))
+            .then_some(siblings)
+    }
+}
+
+impl Document {
+    /// Drops a named block. In lossless documents the text around it stays.
+    pub fn remove_block(&mut self, name: &str) -> Result<Block, Error> {
+        if name == DEFAULT_BLOCK_NAME {
+            return Err(Error::AccessError(AccessErrors::DefaultBlockReserved));
+        }
+        let Some(index) = self.get_index(name) else {
+            return Err(Error::AccessError(AccessErrors::BlockNotFound(
+                name.to_string(),
+            )));
+        };
+        if let Some(slots) = self.slots.as_mut() {
+            slots.remove(index - 1);
+        }
+        Ok(self.blocks.shift_remove_index(index).unwrap())
+    }
+}
+
+impl Document {
+    /// Adds an empty block after the others.
+    pub fn create_block(&mut self, name: &str) -> Result<(), Error> {
+        self.check_new_block(name)?;
+        self.append_block(Block::new(name))
+    }
+    /// Gives a block a new name without moving it.
+    pub fn rename_block(&mut self, from: &str, to: &str) -> Result<(), Error> {
+        if from == DEFAULT_BLOCK_NAME {
+            return Err(Error::AccessError(AccessErrors::DefaultBlockReserved));
+        }
+        let Some(index) = self.get_index(from) else {
+            return Err(Error::AccessError(AccessErrors::BlockNotFound(
+                from.to_string(),
+            )));
+        };
+        self.check_new_block(to)?;
+        let renamed = self.blocks[index].renamed(to);
+        self.blocks.replace_index(index, renamed).unwrap();
+        Ok(())
+    }
+    /// Adds a copy of a block, comments and all, after the others.
+    pub fn copy_block(&mut self, from: &str, to: &str) -> Result<(), Error> {
+        if from == DEFAULT_BLOCK_NAME {
+            return Err(Error::AccessError(AccessErrors::DefaultBlockReserved));
+        }
+        let Some(block) = self.get_block(from) else {
+            return Err(Error::AccessError(AccessErrors::BlockNotFound(
+                from.to_string(),
+            )));
+        };
+        let copy = block.renamed(to);
+        self.check_new_block(to)?;
+        self.append_block(copy)
+    }
+    fn check_new_block(&self, name: &str) -> Result<(), Error> {
+        if name == DEFAULT_BLOCK_NAME {
+            return Err(Error::AccessError(AccessErrors::DefaultBlockReserved));
+        }
+        if self.get_index(name).is_some() {
+            return Err(Error::AccessError(AccessErrors::BlockAlreadyExists(
+                name.to_string(),
+            )));
+        }
+        Ok(())
+    }
+    /// In lossless documents a blank line sets the new block apart from the
+    /// text before it.
+    fn append_block(&mut self, block: Block) -> Result<(), Error> {
+        let written = self.blocks.len() > 1 || self.blocks[0].lines_len() > 0;
+        if self.slots.is_some() && written {
+            self.get_default_block_mut()?.add_blank("\n");
+        }
+        self.add_block(block)
+    }
+}
+
+impl Document {
+    /// Sets a variable in the named block. An existing one keeps its place,
+    /// a new one goes after the block's last variable; in the default block
+    /// of a lossless document with no variables yet, that is right before the
+    /// first named block.
+    pub fn set_variable(&mut self, block_name: &str, variable: Variable) -> Result<(), Error> {
+        let first_slot = self.slots.as_ref().and_then(|slots| slots.first().copied());
+        let block = self.get_block_mut(block_name)?;
+        if block.get_variable(&variable.key).is_some() {
+            let key = variable.key.clone();
+            return block.replace_variable(&key, variable);
+        }
+        let index = match block.after_last_variable() {
+            Some(index) => index,
+            None if block_name == DEFAULT_BLOCK_NAME => first_slot.unwrap_or(block.lines_len()),
+            None => block.lines_len(),
+        };
+        block.insert_variable(index, variable)?;
+        if block_name == DEFAULT_BLOCK_NAME {
+            self.shift_slots(index, 1);
+        }
+        Ok(())
+    }
+    pub fn remove_variable(&mut self, block_name: &str, key: &str) -> Result<Variable, Error> {
+        let (index, variable) = self.get_block_mut(block_name)?.remove_variable(key)?;
+        if block_name == DEFAULT_BLOCK_NAME {
+            self.shift_slots(index + 1, -1);
+        }
+        Ok(variable)
+    }
+    /// Keeps named blocks next to the same default block lines after lines
+    /// from `index` on moved by `by`.
+    fn shift_slots(&mut self, index: usize, by: isize) {
+        for slot in self.slots.iter_mut().flatten() {
+            if *slot >= index {
+                *slot = slot.saturating_add_signed(by);
+            }
+        }
+    }
+}
+
+impl Document {
+    /// The environment the file sets up: every key with the variable that
+    /// wins it. Variables apply top to bottom, as a loader reads them, so a
+    /// later one overrides an earlier one, and variables commented out in
+    /// disabled blocks do not apply at all. Keys stay in the order they were
+    /// first set.
+    pub fn resolve(&self) -> IndexMap<&str, (&Block, &Variable)> {
+        let mut resolved = IndexMap::new();
+        for (block, variables) in self.applied() {
+            for variable in variables {
+                resolved.insert(variable.key.as_str(), (block, variable));
+            }
+        }
+        resolved
+    }
+    /// The variables a loader reads, in the order they apply: those commented
+    /// out in disabled blocks are left out.
+    fn applied(&self) -> impl Iterator<Item = (&Block, impl Iterator<Item = &Variable>)> {
+        self.sections().into_iter().map(|(block, lines)| {
+            let variables = lines.into_iter().filter_map(|line| match line {
+                Line::Variable(variable) if !variable.disabled => Some(variable),
+                _ => None,
+            });
+            (block, variables)
+        })
+    }
+    /// Every definition of `key` a loader reads, in the order they appear in
+    /// the file. The last one is the value `resolve` picks.
+    pub fn explain(&self, key: &str) -> Vec<(&Block, &Variable)> {
+        self.applied()
+            .flat_map(|(block, variables)| {
+                variables
+                    .filter(|variable| variable.key == key)
+                    .map(move |variable| (block, variable))
+            })
+            .collect()
+    }
+}
+
+impl Document {
+    /// The document in the order it is written, as runs of default block
+    /// lines between the named blocks. Without slots the default block comes
+    /// first, the way it is rendered.
+    fn sections(&self) -> Vec<(&Block, Vec<&Line>)> {
+        fn whole(block: &Block) -> (&Block, Vec<&Line>) {
+            (block, block.lines().collect())
+        }
+        let Some(slots) = &self.slots else {
+            return self.blocks.iter().map(whole).collect();
+        };
+        let default_block = &self.blocks[0];
+        let mut named_blocks = self.blocks.iter().skip(1);
+        let mut slots = slots.iter().peekable();
+        let mut sections = Vec::new();
+        let mut run = Vec::new();
+        for (idx, line) in default_block.lines().enumerate() {
+            while slots.next_if(|&&slot| slot <= idx).is_some() {
+                if let Some(block) = named_blocks.next() {
+                    sections.push((default_block, std::mem::take(&mut run)));
+                    sections.push(whole(block));
+                }
+            }
+            run.push(line);
+        }
+        sections.push((default_block, run));
+        sections.extend(named_blocks.map(whole));
+        sections
+    }
+    fn source(&self) -> String {
+        let mut out = String::new();
+        for (block, lines) in self.sections() {
+            if block.name != DEFAULT_BLOCK_NAME {
+                push_source(&mut out, &block.source());
+                continue;
+            }
+            for line in lines {
+                push_source(&mut out, &line.source());
+            }
+        }
+        out
+    }
 }
 
 impl Display for Document {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.slots.is_some() {
+            return write!(f, "{}", self.source());
+        }
         writeln!(
             f,
             "{}",
@@ -181,21 +576,374 @@ mod tests {
             doc.pick("test").unwrap();
             assert_eq!(doc.blocks.last().unwrap().name, "test");
         }
+
+        #[test]
+        fn pick_all_in_order() {
+            let mut doc = Document::new();
+            for name in ["a", "b", "c", "d"] {
+                doc.add_block(Block::new(name)).unwrap();
+            }
+            doc.pick_all(&["c".to_string(), "a".to_string()]).unwrap();
+            let names = doc
+                .get_blocks()
+                .iter()
+                .map(|block| block.name.clone())
+                .collect::<Vec<_>>();
+            assert_eq!(names, [DEFAULT_BLOCK_NAME, "b", "d", "c", "a"]);
+
+            assert!(
+                doc.pick_all(&["b".to_string(), "nope".to_string()])
+                    .is_err()
+            );
+            assert_eq!(doc.get_index("b"), Some(1));
+        }
+
+        #[test]
+        fn is_picked_after_pick_all() {
+            let mut doc = Document::new();
+            for name in ["a", "b", "c"] {
+                doc.add_block(Block::new(name)).unwrap();
+            }
+            let names = ["a".to_string(), "b".to_string()];
+            assert!(!doc.is_picked(&names));
+            doc.pick_all(&names).unwrap();
+            assert!(doc.is_picked(&names));
+            assert!(doc.is_picked(&["b".to_string()]));
+            assert!(!doc.is_picked(&["a".to_string()]));
+            assert!(!doc.is_picked(&["missing".to_string()]));
+            assert!(!doc.is_picked(&[]));
+        }
+
+        #[test]
+        fn axes_need_shared_keys() {
+            let mut doc = Document::new();
+            for (name, key) in [
+                ("prod_database", "DB_HOST"),
+                ("dev_database", "DB_HOST"),
+                ("prod_cache", "CACHE_URL"),
+                ("dev_cache", "CACHE_URL"),
+            ] {
+                let mut block = Block::new(name);
+                block.add_variable(Variable::new(key, "")).unwrap();
+                doc.add_block(block).unwrap();
+            }
+            doc.get_block_mut("dev_cache").unwrap().set_enabled(false);
+            let axes = doc
+                .axes()
+                .into_iter()
+                .map(|(axis, blocks)| {
+                    let names = blocks.iter().map(|block| block.name.as_str());
+                    (axis.to_string(), names.collect::<Vec<_>>())
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                axes,
+                [(
+                    "*_database".to_string(),
+                    vec!["prod_database", "dev_database"]
+                )]
+            );
+        }
+
+        #[test]
+        fn switch_environment() {
+            let mut doc = Document::new();
+            for (name, key) in [
+                ("prod_database", "DB_HOST"),
+                ("dev_database", "DB_HOST"),
+                ("email_dev", "SMTP_HOST"),
+                ("email_prod", "SMTP_HOST"),
+                ("prod_cache", "CACHE_URL"),
+            ] {
+                let mut block = Block::new(name);
+                block.add_variable(Variable::new(key, "")).unwrap();
+                doc.add_block(block).unwrap();
+            }
+            let switched = doc.switch("prod").unwrap();
+            assert_eq!(
+                switched,
+                [
+                    (
+                        "prod_database".to_string(),
+                        Some("dev_database".to_string())
+                    ),
+                    ("email_prod".to_string(), None),
+                ]
+            );
+            let names = doc
+                .get_blocks()
+                .iter()
+                .map(|block| block.name.clone())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                names,
+                [
+                    DEFAULT_BLOCK_NAME,
+                    "dev_database",
+                    "email_dev",
+                    "prod_cache",
+                    "prod_database",
+                    "email_prod"
+                ]
+            );
+            assert!(doc.switch("staging").is_err());
+        }
+
+        #[test]
+        fn pick_stays_within_group() {
+            let mut doc = Document::new();
+            for (name, group) in [
+                ("prod_database", Some("database")),
+                ("dev_database", Some("database")),
+                ("local", None),
+            ] {
+                let mut block = Block::new(name);
+                block.group = group.map(ToString::to_string);
+                doc.add_block(block).unwrap();
+            }
+            doc.pick("prod_database").unwrap();
+            let names = doc
+                .get_blocks()
+                .iter()
+                .map(|block| block.name.clone())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                names,
+                [DEFAULT_BLOCK_NAME, "dev_database", "prod_database", "local"]
+            );
+            doc.pick("prod_database").unwrap();
+            assert_eq!(doc.get_index("prod_database"), Some(2));
+            assert_eq!(
+                doc.groups()["database"].last().unwrap().name,
+                "prod_database"
+            );
+        }
+
+        #[test]
+        fn resolve_last_block_wins() {
+            let mut doc = Document::new();
+            let default_block = doc.get_default_block_mut().unwrap();
+            default_block.add_variable(Variable::new("A", "0")).unwrap();
+            default_block.add_variable(Variable::new("B", "0")).unwrap();
+            let mut dev = Block::new("dev");
+            dev.add_variable(Variable::new("B", "1")).unwrap();
+            dev.add_variable(Variable::new("C", "1")).unwrap();
+            doc.add_block(dev).unwrap();
+            let mut prod = Block::new("prod");
+            prod.add_variable(Variable::new("B", "2")).unwrap();
+            doc.add_block(prod).unwrap();
+
+            let resolved = doc
+                .resolve()
+                .into_iter()
+                .map(|(key, (block, variable))| (key, block.name.as_str(), variable.value.as_str()))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                resolved,
+                vec![("A", "default", "0"), ("B", "prod", "2"), ("C", "dev", "1")]
+            );
+
+            let chain = doc
+                .explain("B")
+                .into_iter()
+                .map(|(block, variable)| (block.name.as_str(), variable.value.as_str()))
+                .collect::<Vec<_>>();
+            assert_eq!(chain, vec![("default", "0"), ("dev", "1"), ("prod", "2")]);
+            assert!(doc.explain("D").is_empty());
+        }
+
+        #[test]
+        fn resolve_skips_disabled_blocks() {
+            let mut doc = Document::new();
+            let mut dev = Block::new("dev");
+            dev.add_variable(Variable::new("A", "1")).unwrap();
+            doc.add_block(dev).unwrap();
+            let mut prod = Block::new("prod");
+            prod.add_variable(Variable::new("A", "2")).unwrap();
+            doc.add_block(prod).unwrap();
+
+            doc.set_enabled("prod", false).unwrap();
+            assert_eq!(doc.resolve()["A"].0.name, "dev");
+            assert_eq!(doc.explain("A").len(), 1);
+            assert!(doc.set_enabled(DEFAULT_BLOCK_NAME, false).is_err());
+            doc.set_enabled("prod", true).unwrap();
+            assert_eq!(doc.resolve()["A"].0.name, "prod");
+        }
     }
 
     #[cfg(test)]
-    mod display {
+    mod lossless {
         use super::*;
+        use crate::parser::tokens::Comment;
+        use crate::parser::tokens::variable::Variable;
+
+        fn sample() -> Document {
+            let mut doc = Document::lossless();
+            doc.get_default_block_mut()
+                .unwrap()
+                .add_comment(Comment::new("top").with_source("#top\n"));
+            doc.get_default_block_mut().unwrap().add_blank("\n");
+            let mut first = Block::new("first").with_header("#@ first\n");
+            first
+                .add_variable(Variable::new("A", "1").with_source("A = 1\n"))
+                .unwrap();
+            doc.add_block(first.with_footer("##\n")).unwrap();
+            doc.get_default_block_mut().unwrap().add_blank("\n");
+            let mut second = Block::new("second").with_header("#@ second\n");
+            second
+                .add_variable(Variable::new("B", "2").with_source("B=2\n"))
+                .unwrap();
+            doc.add_block(second.with_footer("##")).unwrap();
+            doc
+        }
 
         #[test]
-        fn empty() {
-            let doc = Document::new();
+        fn round_trip() {
+            assert_eq!(
+                sample().to_string(),
+                "#top\n\n#@ first\nA = 1\n##\n\n#@ second\nB=2\n##"
+            );
+        }
+
+        #[test]
+        fn pick_only_moves_block_text() {
+            let mut doc = sample();
+            doc.pick("first").unwrap();
             assert_eq!(
                 doc.to_string(),
-                format!("{0}\n", Block::default().to_string())
+                "#top\n\n#@ second\nB=2\n##\n\n#@ first\nA = 1\n##\n"
             );
         }
 
+        #[test]
+        fn set_and_remove_default_variables() {
+            let mut doc = sample();
+            doc.set_variable(DEFAULT_BLOCK_NAME, Variable::new("TOP", "1"))
+                .unwrap();
+            assert_eq!(
+                doc.to_string(),
+                "#top\n\nTOP=1\n#@ first\nA = 1\n##\n\n#@ second\nB=2\n##"
+            );
+            doc.set_variable(DEFAULT_BLOCK_NAME, Variable::new("NEXT", "2"))
+                .unwrap();
+            doc.set_variable("second", Variable::new("B", "3")).unwrap();
+            doc.set_variable("second", Variable::new("C", "4")).unwrap();
+            assert_eq!(
+                doc.to_string(),
+                "#top\n\nTOP=1\nNEXT=2\n#@ first\nA = 1\n##\n\n#@ second\nB=3\nC=4\n##"
+            );
+            doc.remove_variable(DEFAULT_BLOCK_NAME, "TOP").unwrap();
+            doc.remove_variable(DEFAULT_BLOCK_NAME, "NEXT").unwrap();
+            doc.remove_variable("first", "A").unwrap();
+            assert_eq!(
+                doc.to_string(),
+                "#top\n\n#@ first\n##\n\n#@ second\nB=3\nC=4\n##"
+            );
+        }
+
+        #[test]
+        fn resolve_in_file_order() {
+            let mut doc = sample();
+            doc.get_default_block_mut()
+                .unwrap()
+                .add_variable(Variable::new("A", "trailing"))
+                .unwrap();
+            doc.pick("first").unwrap();
+            let resolved = doc
+                .resolve()
+                .into_iter()
+                .map(|(key, (block, variable))| (key, block.name.as_str(), variable.value.as_str()))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                resolved,
+                [("B", "second", "2"), ("A", "default", "trailing")]
+            );
+            let chain = doc
+                .explain("A")
+                .into_iter()
+                .map(|(block, variable)| (block.name.as_str(), variable.value.as_str()))
+                .collect::<Vec<_>>();
+            assert_eq!(chain, [("first", "1"), ("default", "trailing")]);
+        }
+
+        #[test]
+        fn remove_block_keeps_surrounding_text() {
+            let mut doc = sample();
+            doc.remove_block("first").unwrap();
+            assert_eq!(doc.to_string(), "#top\n\n\n#@ second\nB=2\n##");
+            assert!(doc.remove_block("first").is_err());
+        }
+
+        #[test]
+        fn move_block_only_moves_block_text() {
+            let mut doc = sample();
+            doc.add_block(Block::new("third")).unwrap();
+            doc.move_block("third", 1).unwrap();
+            assert_eq!(
+                doc.to_string(),
+                "#top\n\n#@ third\n##\n\n#@ first\nA = 1\n##\n#@ second\nB=2\n##"
+            );
+            doc.move_block("third", 10).unwrap();
+            assert_eq!(doc.get_index("third"), Some(3));
+            assert!(doc.move_block(DEFAULT_BLOCK_NAME, 2).is_err());
+            assert!(doc.move_block("missing", 2).is_err());
+        }
+
+        #[test]
+        fn create_block_after_a_blank_line() {
+            let mut doc = sample();
+            doc.create_block("third").unwrap();
+            assert!(
+                doc.to_string()
+                    .ends_with("#@ second\nB=2\n##\n\n#@ third\n##\n")
+            );
+            assert!(doc.create_block("third").is_err());
+            assert!(doc.create_block(DEFAULT_BLOCK_NAME).is_err());
+        }
+
+        #[test]
+        fn rename_block_in_place() {
+            let mut doc = sample();
+            doc.rename_block("first", "renamed").unwrap();
+            assert_eq!(
+                doc.to_string(),
+                "#top\n\n#@ renamed\nA = 1\n##\n\n#@ second\nB=2\n##"
+            );
+            assert!(doc.get_block("first").is_none());
+            assert!(doc.rename_block("renamed", "second").is_err());
+            assert!(doc.rename_block("missing", "other").is_err());
+            assert!(doc.rename_block(DEFAULT_BLOCK_NAME, "other").is_err());
+        }
+
+        #[test]
+        fn copy_block_keeps_its_text() {
+            let mut doc = sample();
+            doc.copy_block("first", "copy").unwrap();
+            assert_eq!(
+                doc.to_string(),
+                "#top\n\n#@ first\nA = 1\n##\n\n#@ second\nB=2\n##\n\n#@ copy\nA = 1\n##\n"
+            );
+            assert!(doc.copy_block("first", "second").is_err());
+        }
+
+        #[test]
+        fn added_blocks_go_last() {
+            let mut doc = sample();
+            doc.add_block(Block::new("third")).unwrap();
+            assert!(doc.to_string().ends_with("##\n#@ third\n##\n"));
+        }
+    }
+
+    #[cfg(test)]
+    mod display {
+        use super::*;
+
+        #[test]
+        fn empty() {
+            let doc = Document::new();
+            assert_eq!(doc.to_string(), format!("{0}\n", Block::default()));
+        }
+
         #[test]
         fn with_1_block() {
             let mut doc = Document::new();
@@ -203,11 +951,7 @@ mod tests {
             doc.add_block(Block::new("test")).unwrap();
             assert_eq!(
                 doc.to_string(),
-                format!(
-                    "{0}\n\n{1}\n",
-                    Block::default().to_string(),
-                    Block::new("test").to_string()
-                )
+                format!("{0}\n\n{1}\n", Block::default(), Block::new("test"))
             );
         }
 
@@ -222,9 +966,9 @@ mod tests {
                 doc.to_string(),
                 format!(
                     "{0}\n\n{1}\n\n{2}\n",
-                    Block::default().to_string(),
-                    Block::new("test").to_string(),
-                    Block::new("test2").to_string()
+                    Block::default(),
+                    Block::new("test"),
+                    Block::new("test2")
                 )
             );
         }
@@ -1,17 +1,35 @@
+use crate::parser::tokens::comment::Comment;
+use crate::parser::tokens::directive::Directive;
 use crate::parser::tokens::variable::Variable;
 use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
 
-#[derive(Clone, Debug, Eq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub enum Line {
-    Comment(String),
+    Comment(Comment),
+    Directive(Directive),
     Variable(Variable),
+    Blank(String),
+}
+
+impl Line {
+    pub fn source(&self) -> String {
+        match self {
+            Line::Comment(comment) => comment.source(),
+            Line::Directive(directive) => directive.source(),
+            Line::Variable(variable) => variable.source(),
+            Line::Blank(source) => source.clone(),
+        }
+    }
 }
 
 impl Display for Line {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Line::Comment(comment) => write!(f, "# {comment}"),
+            Line::Comment(comment) => write!(f, "{comment}"),
+            Line::Directive(directive) => write!(f, "{directive}"),
             Line::Variable(variable) => write!(f, "{variable}"),
+            Line::Blank(_) => Ok(()),
         }
     }
 }
@@ -25,6 +43,17 @@ impl PartialEq for Line {
     }
 }
 
+impl Hash for Line {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Line::Comment(comment) => comment.hash(state),
+            Line::Directive(directive) => directive.hash(state),
+            Line::Variable(variable) => variable.hash(state),
+            Line::Blank(source) => source.hash(state),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -40,8 +69,13 @@ mod tests {
 
     #[test]
     fn line_inequality_by_comment() {
-        let line1 = Line::Comment("comment".to_string());
-        let line2 = Line::Comment("comment".to_string());
+        let line1 = Line::Comment(Comment::new("comment"));
+        let line2 = Line::Comment(Comment::new("comment"));
         assert_ne!(line1, line2);
     }
+
+    #[test]
+    fn line_inequality_by_blank() {
+        assert_ne!(Line::Blank("\n".to_string()), Line::Blank("\n".to_string()));
+    }
 }
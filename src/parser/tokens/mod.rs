@@ -1,7 +1,16 @@
+mod axis;
 mod block;
+mod comment;
+pub mod directive;
 mod document;
 mod line;
+pub mod quote;
+mod source;
+mod span;
 pub mod variable;
 
 pub use block::Block;
+pub use comment::Comment;
+pub use directive::Directive;
 pub use document::Document;
+pub use span::Span;
@@ -0,0 +1,275 @@
+use crate::parser::constants::COMMENT_SYMBOL;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Quote {
+    None,
+    Single,
+    Double,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum QuoteError {
+    Unterminated,
+    /// Byte offset, within the decoded text, of what follows the quote.
+    UnexpectedCharacters(usize),
+}
+
+impl Quote {
+    /// Decodes the text written after `=` the way common dotenv loaders do:
+    /// unquoted values are trimmed and end at ` #`, single-quoted values are
+    /// literal (`''` stands for one `'`), and double-quoted values understand
+    /// `\n`, `\r`, `\t`, `\"` and `\\`. Quoted values may span several lines.
+    /// The inline comment following the value, if any, is returned alongside.
+    pub fn decode(raw: &str) -> Result<(String, Quote, Option<String>), QuoteError> {
+        let trimmed = raw.trim_start();
+        let (value, rest, quote) = if let Some(body) = trimmed.strip_prefix('"') {
+            let (value, rest) = Self::decode_double(body, "")?;
+            (value, rest, Quote::Double)
+        } else if let Some(body) = trimmed.strip_prefix('\'') {
+            let (value, rest) = Self::decode_single(body)?;
+            (value, rest, Quote::Single)
+        } else {
+            let (value, comment) = Self::split_inline_comment(trimmed);
+            return Ok((value.trim_end().to_string(), Quote::None, comment));
+        };
+        let rest = rest.trim_start();
+        if rest.is_empty() {
+            Ok((value, quote, None))
+        } else if let Some(comment) = rest.strip_prefix(COMMENT_SYMBOL) {
+            Ok((value, quote, Some(comment.trim().to_string())))
+        } else {
+            Err(QuoteError::UnexpectedCharacters(raw.len() - rest.len()))
+        }
+    }
+
+    /// The body of a double-quoted value decoded with the escapes in `kept`
+    /// left as written, for a later pass to read; `None` for other quoting.
+    pub fn decode_keeping(raw: &str, kept: &str) -> Option<String> {
+        let body = raw.trim_start().strip_prefix('"')?;
+        Self::decode_double(body, kept).ok().map(|(value, _)| value)
+    }
+
+    /// The quoting a new value needs to read back the same: none for plain
+    /// words, single quotes for anything a loader or `${VAR}` expansion would
+    /// otherwise interpret.
+    pub fn fitting(value: &str) -> Quote {
+        if value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.,:/@+%=~^".contains(c))
+        {
+            Quote::None
+        } else {
+            Quote::Single
+        }
+    }
+
+    /// Writes `value` back in this quoting style. Line breaks stay literal so
+    /// multiline values keep their shape, and a backslash is only escaped
+    /// where it would otherwise start an escape sequence.
+    pub fn encode(&self, value: &str) -> String {
+        match self {
+            Quote::None => value.to_string(),
+            Quote::Single => format!("'{}'", value.replace('\'', "''")),
+            Quote::Double => {
+                let mut encoded = String::from('"');
+                let mut chars = value.chars().peekable();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' if chars
+                            .peek()
+                            .is_none_or(|next| matches!(next, 'n' | 'r' | 't' | '"' | '\\')) =>
+                        {
+                            encoded.push_str("\\\\")
+                        }
+                        '"' => encoded.push_str("\\\""),
+                        '\r' => encoded.push_str("\\r"),
+                        c => encoded.push(c),
+                    }
+                }
+                encoded.push('"');
+                encoded
+            }
+        }
+    }
+
+    fn decode_double<'a>(body: &'a str, kept: &str) -> Result<(String, &'a str), QuoteError> {
+        let mut value = String::new();
+        let mut chars = body.char_indices();
+        while let Some((idx, c)) = chars.next() {
+            match c {
+                '"' => return Ok((value, &body[idx + 1..])),
+                '\\' => match chars.next() {
+                    Some((_, escaped)) if kept.contains(escaped) => {
+                        value.push('\\');
+                        value.push(escaped);
+                    }
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, 'r')) => value.push('\r'),
+                    Some((_, 't')) => value.push('\t'),
+                    Some((_, '"')) => value.push('"'),
+                    Some((_, '\\')) => value.push('\\'),
+                    Some((_, other)) => {
+                        value.push('\\');
+                        value.push(other);
+                    }
+                    None => return Err(QuoteError::Unterminated),
+                },
+                c => value.push(c),
+            }
+        }
+        Err(QuoteError::Unterminated)
+    }
+
+    fn decode_single(body: &str) -> Result<(String, &str), QuoteError> {
+        let mut value = String::new();
+        let mut chars = body.char_indices().peekable();
+        while let Some((idx, c)) = chars.next() {
+            if c != '\'' {
+                value.push(c);
+            } else if chars.next_if(|&(_, next)| next == '\'').is_some() {
+                value.push('\'');
+            } else {
+                return Ok((value, &body[idx + 1..]));
+            }
+        }
+        Err(QuoteError::Unterminated)
+    }
+
+    fn split_inline_comment(value: &str) -> (&str, Option<String>) {
+        let mut previous = None;
+        for (idx, c) in value.char_indices() {
+            if previous.is_some_and(char::is_whitespace) && value[idx..].starts_with(COMMENT_SYMBOL)
+            {
+                let comment = value[idx..].trim_start_matches(COMMENT_SYMBOL).trim();
+                return (&value[..idx], Some(comment.to_string()));
+            }
+            previous = Some(c);
+        }
+        (value, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(test)]
+    mod decode {
+        use super::*;
+
+        #[test]
+        fn unquoted() {
+            assert_eq!(
+                Quote::decode("  some value  ").unwrap(),
+                ("some value".to_string(), Quote::None, None)
+            );
+        }
+
+        #[test]
+        fn unquoted_with_inline_comment() {
+            assert_eq!(
+                Quote::decode("value # note").unwrap(),
+                ("value".to_string(), Quote::None, Some("note".to_string()))
+            );
+            assert_eq!(
+                Quote::decode("abc#def").unwrap(),
+                ("abc#def".to_string(), Quote::None, None)
+            );
+        }
+
+        #[test]
+        fn double_quoted_with_escapes() {
+            assert_eq!(
+                Quote::decode(r#""a b\n\"c\" \\ \$d""#).unwrap(),
+                ("a b\n\"c\" \\ \\$d".to_string(), Quote::Double, None)
+            );
+        }
+
+        #[test]
+        fn keeping_escapes() {
+            assert_eq!(
+                Quote::decode_keeping(r#" "a\tb \\ \$c" # note"#, "\\$").unwrap(),
+                "a\tb \\\\ \\$c"
+            );
+            assert_eq!(Quote::decode_keeping("'$c'", "\\$"), None);
+        }
+
+        #[test]
+        fn single_quoted_is_literal() {
+            assert_eq!(
+                Quote::decode(r"'it''s \n'").unwrap(),
+                (r"it's \n".to_string(), Quote::Single, None)
+            );
+        }
+
+        #[test]
+        fn multiline() {
+            assert_eq!(
+                Quote::decode("\"-----BEGIN-----\nabc\n-----END-----\"").unwrap(),
+                (
+                    "-----BEGIN-----\nabc\n-----END-----".to_string(),
+                    Quote::Double,
+                    None
+                )
+            );
+        }
+
+        #[test]
+        fn comment_after_quote() {
+            assert_eq!(
+                Quote::decode("'a # b' # note").unwrap(),
+                ("a # b".to_string(), Quote::Single, Some("note".to_string()))
+            );
+        }
+
+        #[test]
+        fn unterminated() {
+            assert_eq!(Quote::decode("\"abc"), Err(QuoteError::Unterminated));
+            assert_eq!(Quote::decode("'abc"), Err(QuoteError::Unterminated));
+            assert_eq!(Quote::decode("\"abc\\"), Err(QuoteError::Unterminated));
+        }
+
+        #[test]
+        fn unexpected_characters() {
+            assert_eq!(
+                Quote::decode("\"abc\"def"),
+                Err(QuoteError::UnexpectedCharacters(5))
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod encode {
+        use super::*;
+
+        #[test]
+        fn round_trip() {
+            let value = "it's \"quoted\"\nand \\ more\r\\$HOME \\";
+            for quote in [Quote::Single, Quote::Double] {
+                assert_eq!(
+                    Quote::decode(&quote.encode(value)).unwrap(),
+                    (value.to_string(), quote, None)
+                );
+            }
+        }
+
+        #[test]
+        fn fitting_quote_reads_back() {
+            assert_eq!(Quote::fitting("postgres://db:5432/app"), Quote::None);
+            assert_eq!(Quote::fitting(""), Quote::None);
+            for value in ["two words", "a #b", "$HOME", "'x'", "line\nbreak", " pad"] {
+                let quote = Quote::fitting(value);
+                assert_eq!(quote, Quote::Single);
+                assert_eq!(
+                    Quote::decode(&quote.encode(value)).unwrap(),
+                    (value.to_string(), quote, None)
+                );
+            }
+        }
+
+        #[test]
+        fn keeps_unknown_escapes() {
+            assert_eq!(Quote::Double.encode("\\$HOME"), "\"\\$HOME\"");
+        }
+    }
+}
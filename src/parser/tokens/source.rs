@@ -0,0 +1,28 @@
+/// Appends a piece of source text, making sure the previous piece ends its
+/// line first. Only the last line of a file may lack a line break, and it
+/// can end up in the middle once blocks are reordered.
+pub fn push_source(out: &mut String, source: &str) {
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(source);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_existing_line_breaks() {
+        let mut out = String::from("A=1\r\n");
+        push_source(&mut out, "B=2");
+        assert_eq!(out, "A=1\r\nB=2");
+    }
+
+    #[test]
+    fn ends_unterminated_line() {
+        let mut out = String::from("##");
+        push_source(&mut out, "#@ block\n");
+        assert_eq!(out, "##\n#@ block\n");
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt::{Display, Formatter};
+
+/// Where a token or an error starts in the source: the byte offset along with
+/// the zero-based line and column. Columns count characters, not bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    pub fn new(offset: usize, line: usize, column: usize) -> Self {
+        Span {
+            offset,
+            line,
+            column,
+        }
+    }
+    /// The position right after `text`, which starts at this one.
+    pub fn shifted(self, text: &str) -> Self {
+        let Some((before, last_line)) = text.rsplit_once('\n') else {
+            return Span {
+                offset: self.offset + text.len(),
+                column: self.column + text.chars().count(),
+                ..self
+            };
+        };
+        Span {
+            offset: self.offset + text.len(),
+            line: self.line + before.matches('\n').count() + 1,
+            column: last_line.chars().count(),
+        }
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{0}:{1}", self.line + 1, self.column + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shifted_counts_characters() {
+        let span = Span::new(10, 2, 4).shifted("é=");
+        assert_eq!(span, Span::new(13, 2, 6));
+        assert_eq!(span.to_string(), "3:7");
+    }
+
+    #[test]
+    fn shifted_across_lines() {
+        let span = Span::new(10, 2, 4).shifted("A=\"x\ny\nzé");
+        assert_eq!(span, Span::new(20, 4, 2));
+    }
+}
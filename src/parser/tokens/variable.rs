@@ -1,3 +1,6 @@
+use crate::parser::constants::{COMMENT_SYMBOL, KV_DELIMITER};
+use crate::parser::tokens::quote::{Quote, QuoteError};
+use crate::parser::tokens::span::Span;
 use std::fmt::{Display, Formatter};
 use std::hash::{Hash, Hasher};
 
@@ -5,6 +8,12 @@ use std::hash::{Hash, Hasher};
 pub struct Variable {
     pub key: String,
     pub value: String,
+    pub quote: Quote,
+    pub comment: Option<String>,
+    /// Commented out along with the rest of its disabled block.
+    pub disabled: bool,
+    source: Option<String>,
+    span: Span,
 }
 
 impl Variable {
@@ -12,13 +21,85 @@ impl Variable {
         Variable {
             key: key.to_string(),
             value: value.to_string(),
+            quote: Quote::None,
+            comment: None,
+            disabled: false,
+            source: None,
+            span: Span::default(),
         }
     }
+    pub fn parse(key: &str, raw: &str) -> Result<Self, QuoteError> {
+        let (value, quote, comment) = Quote::decode(raw)?;
+        Ok(Variable {
+            quote,
+            comment,
+            ..Variable::new(key, &value)
+        })
+    }
+    pub fn with_source(mut self, source: &str) -> Self {
+        self.source = Some(source.to_string());
+        self
+    }
+    /// The same variable without its source, so it is written back the
+    /// normalized way.
+    pub fn normalized(&self) -> Self {
+        Variable {
+            source: None,
+            ..self.clone()
+        }
+    }
+    pub fn with_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+    pub fn span(&self) -> Span {
+        self.span
+    }
+    pub fn source(&self) -> String {
+        match &self.source {
+            Some(source) => source.clone(),
+            None => format!("{self}\n"),
+        }
+    }
+    /// The value as `${VAR}` expansion reads it, with `\\` and `\$` still to
+    /// be unescaped: double-quoted values keep them as written, and unquoted
+    /// values, which have no escapes of their own, only escape a `$`.
+    pub fn template(&self) -> String {
+        if self.quote == Quote::Double {
+            let source = self.source();
+            let raw = &source[source.find(KV_DELIMITER).map_or(0, |idx| idx + 1)..];
+            if let Some(template) = Quote::decode_keeping(raw, "\\$") {
+                return template;
+            }
+        }
+        let mut template = String::new();
+        let mut chars = self.value.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '\\' && chars.peek() != Some(&'$') {
+                template.push('\\');
+            }
+            template.push(c);
+        }
+        template
+    }
 }
 
 impl Display for Variable {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{0}={1}", self.key, self.value)
+        write!(
+            f,
+            "{0}{KV_DELIMITER}{1}",
+            self.key,
+            self.quote.encode(&self.value)
+        )?;
+        match &self.comment {
+            Some(comment) => write!(f, " {COMMENT_SYMBOL} {comment}"),
+            None => Ok(()),
+        }
     }
 }
 
@@ -43,10 +124,20 @@ mod tests {
         let v1 = Variable {
             key: "KEY".to_string(),
             value: "value1".to_string(),
+            quote: Quote::None,
+            comment: None,
+            disabled: false,
+            source: None,
+            span: Span::default(),
         };
         let v2 = Variable {
             key: "KEY".to_string(),
             value: "value2".to_string(),
+            quote: Quote::None,
+            comment: None,
+            disabled: false,
+            source: None,
+            span: Span::default(),
         };
         assert_eq!(v1, v2);
     }
@@ -63,10 +154,23 @@ mod tests {
         let v1 = Variable {
             key: "KEY".to_string(),
             value: "value1".to_string(),
+            quote: Quote::None,
+            comment: None,
+            disabled: false,
+            source: None,
+            span: Span::default(),
         };
         let v2 = Variable::new("KEY", "value1");
         assert_eq!(v1, v2);
     }
+
+    #[test]
+    fn parse_decodes_value() {
+        let var = Variable::parse("KEY", " \"a b\" # note").unwrap();
+        assert_eq!(var.value, "a b");
+        assert_eq!(var.quote, Quote::Double);
+        assert_eq!(var.comment.as_deref(), Some("note"));
+    }
 }
 
 #[cfg(test)]
@@ -84,4 +188,23 @@ mod display_tests {
         let var = Variable::new("KEY", "");
         assert_eq!(var.to_string(), "KEY=");
     }
+
+    #[test]
+    fn keeps_original_quoting() {
+        let var = Variable::parse("KEY", "'it''s'").unwrap();
+        assert_eq!(var.value, "it's");
+        assert_eq!(var.to_string(), "KEY='it''s'");
+
+        let var = Variable::parse("KEY", "\"line1\nline2\" # cert").unwrap();
+        assert_eq!(var.to_string(), "KEY=\"line1\nline2\" # cert");
+    }
+
+    #[test]
+    fn source_is_exact() {
+        let var = Variable::parse("KEY", " value ").unwrap();
+        assert_eq!(var.source(), "KEY=value\n");
+        let var = var.with_source("KEY= value \r\n");
+        assert_eq!(var.to_string(), "KEY=value");
+        assert_eq!(var.source(), "KEY= value \r\n");
+    }
 }
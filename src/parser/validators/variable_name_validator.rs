@@ -1,26 +1,32 @@
 use crate::error::{Error, NamingErrors};
+use crate::parser::Span;
 
-pub fn validate_variable_name(line: u16, name: &str) -> Result<(), Error> {
+/// Checks a variable name starting at `span`; errors point at the offending
+/// character.
+pub fn validate_variable_name(span: Span, name: &str) -> Result<(), Error> {
     if name.is_empty() {
-        return Err(Error::NamingError(NamingErrors::VariableNameEmpty));
+        return Err(Error::NamingError(NamingErrors::VariableNameEmpty(span)));
     }
 
-    let mut chars = name.chars();
-    let first = chars.next().unwrap();
+    let mut chars = name.char_indices();
+    let (_, first) = chars.next().unwrap();
 
     // First character: must be letter or underscore
     if !first.is_ascii_alphabetic() && first != '_' {
         return Err(Error::NamingError(
-            NamingErrors::StartsWithInvalidCharacter(line, first.to_string()),
+            NamingErrors::StartsWithInvalidCharacter(span, first.to_string()),
         ));
     }
 
     // Remaining characters: must be letters, digits, or underscores
-    if let Some(invalid_char) =
-        chars.find(|&c| !(c.is_ascii_alphabetic() || c.is_ascii_digit() || c == '_'))
+    if let Some((idx, invalid_char)) =
+        chars.find(|&(_, c)| !(c.is_ascii_alphabetic() || c.is_ascii_digit() || c == '_'))
     {
         return Err(Error::NamingError(
-            NamingErrors::VariableContainsInvalidCharacter(line, invalid_char.to_string()),
+            NamingErrors::VariableContainsInvalidCharacter(
+                span.shifted(&name[..idx]),
+                invalid_char.to_string(),
+            ),
         ));
     }
 
@@ -33,48 +39,58 @@ mod tests {
 
     #[test]
     fn contains_underscore_and_alphanumeric() {
-        validate_variable_name(1, "valid_variable_1_name").unwrap();
+        validate_variable_name(Span::default(), "valid_variable_1_name").unwrap();
     }
 
     #[test]
     fn starts_with_underscore() {
-        validate_variable_name(1, "_valid_variable_name").unwrap();
+        validate_variable_name(Span::default(), "_valid_variable_name").unwrap();
     }
 
     #[test]
     #[should_panic]
     fn empty() {
-        validate_variable_name(1, "").unwrap();
+        validate_variable_name(Span::default(), "").unwrap();
     }
 
     #[test]
     #[should_panic]
     fn starts_with_digit() {
-        validate_variable_name(1, "1invalid_variable_name").unwrap();
+        validate_variable_name(Span::default(), "1invalid_variable_name").unwrap();
     }
 
     #[test]
     #[should_panic]
     fn contains_special_character() {
-        validate_variable_name(1, "invalid-variable-name").unwrap();
-        validate_variable_name(1, "invalid!variable!name").unwrap();
+        validate_variable_name(Span::default(), "invalid-variable-name").unwrap();
+        validate_variable_name(Span::default(), "invalid!variable!name").unwrap();
     }
 
     #[test]
     #[should_panic]
     fn contains_space() {
-        validate_variable_name(1, "invalid variable name").unwrap();
+        validate_variable_name(Span::default(), "invalid variable name").unwrap();
     }
 
     #[test]
     #[should_panic]
     fn starts_with_special_character() {
-        validate_variable_name(1, "!invalid_variable_name").unwrap();
-        validate_variable_name(1, "-invalid_variable_name").unwrap();
+        validate_variable_name(Span::default(), "!invalid_variable_name").unwrap();
+        validate_variable_name(Span::default(), "-invalid_variable_name").unwrap();
     }
 
     #[test]
     fn contains_uppercase_letters() {
-        validate_variable_name(1, "VALID_VARIABLE_NAME").unwrap();
+        validate_variable_name(Span::default(), "VALID_VARIABLE_NAME").unwrap();
+    }
+
+    #[test]
+    fn error_points_at_invalid_character() {
+        let error = validate_variable_name(Span::new(4, 1, 0), "AB-C").unwrap_err();
+        assert!(matches!(
+            error,
+            Error::NamingError(NamingErrors::VariableContainsInvalidCharacter(span, _))
+                if span == Span::new(6, 1, 2)
+        ));
     }
 }
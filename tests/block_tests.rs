@@ -0,0 +1,107 @@
+mod common;
+
+use common::{create_test_env_file, run};
+
+const TEST_CONTENT: &str = r#"APP_NAME=demo
+
+#@  prod_database
+DB_HOST=db.example.com
+##
+"#;
+
+#[test]
+fn block_new_appends_empty_block() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = run(&["block", "new", "staging_database"], temp_file.path());
+
+    assert!(output.status.success());
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        format!("{TEST_CONTENT}\n#@ staging_database\n##\n")
+    );
+}
+
+#[test]
+fn block_rename_keeps_position_and_comment() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = run(
+        &["block", "rename", "prod_database", "live_database"],
+        temp_file.path(),
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        TEST_CONTENT.replace("prod_database", "live_database")
+    );
+}
+
+#[test]
+fn block_copy_appends_copy() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = run(
+        &["block", "copy", "prod_database", "staging_database"],
+        temp_file.path(),
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        format!("{TEST_CONTENT}\n#@  staging_database\nDB_HOST=db.example.com\n##\n")
+    );
+}
+
+#[test]
+fn block_delete_removes_block() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = run(&["block", "delete", "prod_database"], temp_file.path());
+
+    assert!(output.status.success());
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        "APP_NAME=demo\n\n"
+    );
+}
+
+#[test]
+fn block_commands_refuse_bad_names() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    for (args, message) in [
+        (
+            &["block", "new", "prod_database"][..],
+            "Block 'prod_database' already exists",
+        ),
+        (
+            &["block", "new", "Staging"][..],
+            "starts with invalid character 'S'",
+        ),
+        (
+            &["block", "rename", "prod_database", "default"][..],
+            "default block cannot be",
+        ),
+        (
+            &["block", "delete", "default"][..],
+            "default block cannot be",
+        ),
+        (
+            &["block", "copy", "missing", "other"][..],
+            "Block 'missing' was not found",
+        ),
+    ] {
+        let output = run(args, temp_file.path());
+        assert_eq!(output.status.code(), Some(1), "{args:?}");
+        assert!(
+            String::from_utf8_lossy(&output.stderr).contains(message),
+            "{args:?}"
+        );
+    }
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        TEST_CONTENT
+    );
+}
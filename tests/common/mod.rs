@@ -29,4 +29,14 @@ pub fn run_command(args: &[&str]) -> std::process::Output {
         .args(args)
         .output()
         .expect("Failed to execute command")
+}
+
+/// Helper to run a command on a file and return output
+#[allow(unused)]
+pub fn run(args: &[&str], path: &std::path::Path) -> std::process::Output {
+    Command::new(get_binary_path())
+        .args(args)
+        .arg(path)
+        .output()
+        .expect("Failed to execute command")
 }
\ No newline at end of file
@@ -0,0 +1,142 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{create_test_env_file, get_binary_path};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const TEST_CONTENT: &str = r#"APP_NAME=demo
+DB_HOST=localhost
+
+#@ dev_database
+DB_HOST=dev.example.com
+##
+
+#@ prod_database
+DB_HOST="prod example"
+##
+"#;
+
+#[test]
+fn exec_sets_resolved_variables() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = Command::new(get_binary_path())
+        .arg("exec")
+        .arg(temp_file.path())
+        .arg("--")
+        .arg("sh")
+        .arg("-c")
+        .arg("echo \"$APP_NAME $DB_HOST\"")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "demo prod example\n"
+    );
+}
+
+#[test]
+fn exec_picks_in_memory() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = Command::new(get_binary_path())
+        .arg("exec")
+        .arg("--pick")
+        .arg("dev_database")
+        .arg(temp_file.path())
+        .arg("--")
+        .arg("sh")
+        .arg("-c")
+        .arg("echo \"$DB_HOST\"")
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "dev.example.com\n");
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        TEST_CONTENT
+    );
+}
+
+#[test]
+fn exec_returns_exit_code() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = Command::new(get_binary_path())
+        .arg("exec")
+        .arg(temp_file.path())
+        .arg("--")
+        .arg("sh")
+        .arg("-c")
+        .arg("exit 3")
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn exec_passes_stdin_through() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let mut child = Command::new(get_binary_path())
+        .arg("exec")
+        .arg(temp_file.path())
+        .arg("--")
+        .arg("cat")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"from stdin\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "from stdin\n");
+}
+
+#[test]
+fn exec_unknown_command() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = Command::new(get_binary_path())
+        .arg("exec")
+        .arg(temp_file.path())
+        .arg("--")
+        .arg("envmn-no-such-command")
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Error running 'envmn-no-such-command'"));
+}
+
+#[test]
+fn exec_expands_references() {
+    let temp_file = create_test_env_file("HOST=localhost\nURL=http://$HOST:${PORT:-8080}\n");
+
+    let output = Command::new(get_binary_path())
+        .arg("exec")
+        .arg("--expand")
+        .arg(temp_file.path())
+        .arg("--")
+        .arg("sh")
+        .arg("-c")
+        .arg("echo \"$URL\"")
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "http://localhost:8080\n"
+    );
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use common::create_test_env_file;
+
+#[test]
+fn explain_lists_definitions_in_precedence_order() {
+    let test_content = r#"DB_HOST=localhost
+
+#@ dev_database
+DB_HOST=dev.example.com
+##
+
+#@ prod_database
+DB_PORT=5432
+DB_HOST="prod.example.com"
+##
+"#;
+
+    let temp_file = create_test_env_file(test_content);
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("explain")
+        .arg("DB_HOST")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "DB_HOST is set in 3 blocks, the last one wins:\n  line 1  default        DB_HOST=localhost\n  line 4  dev_database   DB_HOST=dev.example.com\n* line 9  prod_database  DB_HOST=\"prod.example.com\"\n"
+    );
+}
+
+#[test]
+fn explain_follows_file_order() {
+    let temp_file = create_test_env_file("#@ dev\nA=dev\n##\nA=trailing\n");
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("explain")
+        .arg("A")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "A is set in 2 blocks, the last one wins:\n  line 2  dev      A=dev\n* line 4  default  A=trailing\n"
+    );
+}
+
+#[test]
+fn explain_unknown_variable() {
+    let temp_file = create_test_env_file("A=1\n");
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("explain")
+        .arg("B")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Variable 'B' is not defined in any block"));
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use common::{create_test_env_file, get_binary_path};
+use std::process::Command;
+
+const TEST_CONTENT: &str = r#"GREETING='it''s $HOME'
+DB_HOST=localhost
+
+#@ prod_database
+DB_HOST="db host; rm -rf /"
+##
+"#;
+
+fn export(shell: &str, path: &std::path::Path) -> String {
+    let output = Command::new(get_binary_path())
+        .arg("export")
+        .arg("--shell")
+        .arg(shell)
+        .arg(path)
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn export_for_each_shell() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    assert_eq!(
+        export("bash", temp_file.path()),
+        "export GREETING='it'\\''s $HOME'\nexport DB_HOST='db host; rm -rf /'\n"
+    );
+    assert_eq!(
+        export("fish", temp_file.path()),
+        "set -gx GREETING 'it\\'s $HOME'\nset -gx DB_HOST 'db host; rm -rf /'\n"
+    );
+    assert_eq!(
+        export("nu", temp_file.path()),
+        "$env.GREETING = \"it's $HOME\"\n$env.DB_HOST = \"db host; rm -rf /\"\n"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn export_evaluates_in_sh() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(format!(
+            "eval \"$('{0}' export '{1}')\"; printf '%s|%s' \"$GREETING\" \"$DB_HOST\"",
+            get_binary_path().display(),
+            temp_file.path().display()
+        ))
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "it's $HOME|db host; rm -rf /"
+    );
+}
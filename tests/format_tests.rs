@@ -89,4 +89,46 @@ fn stdin_takes_priority_over_file() {
     assert!(output.status.success());
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(stdout.contains("STDIN_VAR"));
-}
\ No newline at end of file
+}
+#[test]
+fn format_keeps_quoted_and_multiline_values() {
+    let test_content = "CERT=\"-----BEGIN CERT-----\nabc\n-----END CERT-----\"\nNAME='it''s'  # owner\nJSON=\"{\\\"a\\\": 1}\"\n";
+
+    let mut temp_file = create_test_env_file(test_content);
+
+    let output = Command::new(get_binary_path())
+        .arg("format")
+        .arg(temp_file.path())
+        .stdin(std::process::Stdio::null())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let mut buffer = String::new();
+    temp_file.read_to_string(&mut buffer).unwrap();
+    assert_eq!(
+        buffer,
+        "CERT=\"-----BEGIN CERT-----\nabc\n-----END CERT-----\"\nNAME='it''s' # owner\nJSON=\"{\\\"a\\\": 1}\"\n"
+    );
+}
+
+#[test]
+fn format_refuses_unclosed_block() {
+    let test_content = "KEY=value\n\n#@ prod_database\nDB_HOST=localhost\n";
+
+    let mut temp_file = create_test_env_file(test_content);
+
+    let output = Command::new(get_binary_path())
+        .arg("format")
+        .arg(temp_file.path())
+        .stdin(std::process::Stdio::null())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Block 'prod_database' is never closed"));
+    let mut buffer = String::new();
+    temp_file.read_to_string(&mut buffer).unwrap();
+    assert_eq!(buffer, test_content);
+}
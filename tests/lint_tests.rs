@@ -20,4 +20,397 @@ VAR=test
         .expect("Failed to execute command");
 
     assert!(output.status.success());
-}
\ No newline at end of file
+}
+#[test]
+fn lint_reports_every_error_in_one_pass() {
+    let test_content = r#"KEY=value
+NO_SEPARATOR
+#@ first
+bad-key=1
+#@ second
+A=1
+A=2
+##
+## stray
+"#;
+
+    let temp_file = create_test_env_file(test_content);
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("error: Missing key and optional value separator\n"));
+    assert!(stderr.contains(":2:1\n  |\n2 | NO_SEPARATOR\n  | ^\n"));
+    assert!(stderr.contains("error: Variable name contains invalid characters '-'\n"));
+    assert!(stderr.contains("4 | bad-key=1\n  |    ^\n  = help: use '_' instead of '-'\n"));
+    assert!(stderr.contains("error: Block 'first' can not wrap another block\n"));
+    assert!(stderr.contains("error: Duplicate variable 'A' found in block 'second'\n"));
+    assert!(stderr.contains(":7:1\n"));
+    assert!(stderr.contains("error: Closed block was never opened\n"));
+    assert!(stderr.contains(":9:1\n"));
+}
+
+#[test]
+fn lint_warnings_exit_with_code_1() {
+    let test_content = "#@ block\nVAR=test\n## end of block\n";
+
+    let temp_file = create_test_env_file(test_content);
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("warning: Text after block end is ignored\n"));
+    assert!(stderr.contains("3 | ## end of block\n  |    ^\n"));
+}
+
+#[test]
+fn lint_reports_rule_findings() {
+    let test_content = r#"DEBUG=true
+api_key=secret
+
+#@ empty_block
+# nothing yet
+##
+
+#@ local
+DEBUG=true
+PORT=8080   
+##
+"#;
+
+    let temp_file = create_test_env_file(test_content);
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("warning[empty-block]: Block 'empty_block' has no variables"));
+    assert!(stderr.contains("warning[duplicate-value]: Variable 'DEBUG' in block 'local'"));
+    assert!(stderr.contains("warning[trailing-whitespace]: Value of 'PORT' in block 'local'"));
+    assert!(stderr.contains("warning[lowercase-key]: Variable 'api_key' in block 'default'"));
+}
+
+#[test]
+fn lint_rule_severity_is_configurable() {
+    let test_content = "api_key=secret\n#@ empty_block\n##\n";
+
+    let temp_file = create_test_env_file(test_content);
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .arg(temp_file.path())
+        .args(["--rule", "empty-block=off", "--rule", "lowercase-key=info"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("info[lowercase-key]"));
+    assert!(!stderr.contains("empty-block"));
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .arg(temp_file.path())
+        .args(["--rule", "empty-block=error"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("error[empty-block]: Block 'empty_block' has no variables"));
+}
+
+#[test]
+fn lint_unclosed_block() {
+    let test_content = "KEY=value\n\n#@ prod_database\nDB_HOST=localhost\n";
+
+    let temp_file = create_test_env_file(test_content);
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("error: Block 'prod_database' is never closed\n"));
+    assert!(stderr.contains(":3:1\n  |\n3 | #@ prod_database\n"));
+}
+
+#[test]
+fn lint_unknown_rule() {
+    let temp_file = create_test_env_file("KEY=value\n");
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .arg(temp_file.path())
+        .args(["--rule", "no-such-rule=off"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Unknown lint rule 'no-such-rule'"));
+}
+
+#[test]
+fn lint_suppression_directives() {
+    let test_content = "API_URL=http://localhost\n\n#@ legacy_block\n# envmn-disable lowercase-key\napi_url=http://localhost\n##\n\n#@ dev\n# envmn-disable-next-line duplicate-value\nAPI_URL=http://localhost\n# envmn-disable-next-line empty-block\nDEBUG=true\n##\n";
+
+    let temp_file = create_test_env_file(test_content);
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("lowercase-key"));
+    assert!(!stderr.contains("duplicate-value"));
+    assert!(stderr.contains(
+        "warning[unused-directive]: Directive in block 'dev' disables 'empty-block' but nothing was reported"
+    ));
+}
+
+#[test]
+fn lint_fix_rewrites_file() {
+    let test_content = "API_URL = http://localhost\ndb_host=localhost   \nTOKEN=\"abc\n\n#@ empty_block\n##\n\n#@ dev\n# kept as is\nDEBUG=true\n##\n";
+
+    let temp_file = create_test_env_file(test_content);
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .arg("--fix")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        "API_URL=http://localhost\nDB_HOST=localhost\nTOKEN=\"abc\"\n\n\n#@ dev\n# kept as is\nDEBUG=true\n##\n"
+    );
+}
+
+#[test]
+fn lint_fix_dry_run_prints_diff() {
+    let test_content = "KEY = value\nOTHER=1\n";
+
+    let temp_file = create_test_env_file(test_content);
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .args(["--fix", "--dry-run"])
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("@@ -1,2 +1,2 @@\n-KEY = value\n+KEY=value\n OTHER=1\n"));
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        test_content
+    );
+}
+
+#[test]
+fn lint_fix_refuses_unfixable_errors() {
+    let test_content = "KEY = value\nNOT_A_VARIABLE\n";
+
+    let temp_file = create_test_env_file(test_content);
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .arg("--fix")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot be fixed automatically"));
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        test_content
+    );
+}
+
+#[test]
+fn lint_output_is_plain_when_piped() {
+    let temp_file = create_test_env_file("db-host=localhost\n");
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains('\x1b'));
+    assert!(stderr.contains(&format!(
+        " --> {}:1:3\n  |\n1 | db-host=localhost\n  |   ^\n  = help: use '_' instead of '-'\n",
+        temp_file.path().display()
+    )));
+}
+
+#[test]
+fn lint_json_output() {
+    let temp_file = create_test_env_file("A=1\n#@ dev\na=2\n##\n");
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .arg("--format")
+        .arg("json")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stderr.is_empty());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let diagnostic = &report["diagnostics"][0];
+    assert_eq!(diagnostic["rule"], "lowercase-key");
+    assert_eq!(diagnostic["severity"], "warning");
+    assert_eq!(diagnostic["block"], "dev");
+    assert_eq!(diagnostic["key"], "a");
+    assert_eq!(diagnostic["span"]["line"], 3);
+    assert_eq!(diagnostic["span"]["column"], 1);
+    assert_eq!(diagnostic["fixable"], true);
+}
+
+#[test]
+fn lint_sarif_output() {
+    let temp_file = create_test_env_file("NO_SEPARATOR\n");
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .arg("--format")
+        .arg("sarif")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["version"], "2.1.0");
+    let run = &report["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "envmn");
+    assert_eq!(run["results"][0]["level"], "error");
+    assert_eq!(
+        run["results"][0]["locations"][0]["physicalLocation"]["region"]["startLine"],
+        1
+    );
+}
+
+#[test]
+fn lint_format_conflicts_with_fix() {
+    let temp_file = create_test_env_file("A=1\n");
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .arg("--fix")
+        .arg("--format")
+        .arg("json")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn lint_reference_rules() {
+    let test_content = "URL=http://${HOST}:${ENVMN_TEST_PORT}\n#@ dev\nA=$B\nB=$A\n##\n";
+
+    let temp_file = create_test_env_file(test_content);
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(
+        "warning[undefined-reference]: Variable 'URL' in block 'default' refers to 'HOST', which is not defined\n"
+    ));
+    assert!(stderr.contains(":1:12\n"));
+    assert!(stderr.contains("refers to 'ENVMN_TEST_PORT'"));
+    assert!(stderr.contains(
+        "error[circular-reference]: Variable 'A' in block 'dev' refers to itself: A -> B -> A\n"
+    ));
+}
+
+#[test]
+fn lint_ignores_disabled_blocks_for_references() {
+    let test_content = "B=${Y}\n#@! dev\n#! Y=1\n#! Z=$MISSING\n##\n";
+
+    let temp_file = create_test_env_file(test_content);
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Variable 'B' in block 'default' refers to 'Y', which is not defined"));
+    assert!(!stderr.contains("MISSING"));
+}
+
+#[test]
+fn lint_process_env_counts_as_defined() {
+    let temp_file = create_test_env_file("URL=http://${ENVMN_TEST_HOST}\n");
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .arg("--process-env")
+        .arg(temp_file.path())
+        .env("ENVMN_TEST_HOST", "localhost")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn lint_group_keys() {
+    let test_content = "#@ prod_database group=database\nDB_HOST=db\nDB_PASSWORD=secret\n##\n#@ dev_database group=database\nDB_HOST=localhost\n##\n";
+
+    let temp_file = create_test_env_file(test_content);
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("lint")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(
+        "warning[group-keys]: Block 'dev_database' of group 'database' does not set DB_PASSWORD, which other blocks of the group do\n"
+    ));
+    assert!(stderr.contains(":5:1\n"));
+}
@@ -29,4 +29,85 @@ API_KEY=secret
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(stdout.contains("database_block"));
     assert!(stdout.contains("api_block"));
-}
\ No newline at end of file
+}
+
+#[test]
+fn list_json_output() {
+    let temp_file = create_test_env_file("A=1\n\n#@ dev\nB=2\nC=3\n##\n");
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("list")
+        .arg("--format")
+        .arg("json")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let list: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        list["blocks"],
+        serde_json::json!([
+            {"name": "default", "span": null, "group": null, "enabled": true, "variables": ["A"]},
+            {"name": "dev", "span": {"offset": 5, "line": 3, "column": 1}, "group": null, "enabled": true, "variables": ["B", "C"]},
+        ])
+    );
+}
+
+#[test]
+fn list_json_reports_errors_as_json() {
+    let temp_file = create_test_env_file("#@ dev\nA=1\n");
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("list")
+        .arg("--format")
+        .arg("json")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        report["diagnostics"][0]["message"],
+        "Block 'dev' is never closed"
+    );
+    assert_eq!(report["diagnostics"][0]["block"], "dev");
+}
+
+#[test]
+fn list_shows_active_group_member() {
+    let temp_file = create_test_env_file(
+        "#@ prod_database group=database\nDB_HOST=db\n##\n#@ dev_database group=database\nDB_HOST=localhost\n##\n#@ local\n##\n",
+    );
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("list")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Blocks (4):\n- default\n- prod_database (database)\n- dev_database (database, active)\n- local\n"
+    );
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("list")
+        .arg("--format")
+        .arg("json")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    let list: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        list["blocks"][1]["group"],
+        serde_json::json!({"name": "database", "active": false})
+    );
+    assert_eq!(
+        list["blocks"][2]["group"],
+        serde_json::json!({"name": "database", "active": true})
+    );
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use common::{create_test_env_file, run};
+
+const TEST_CONTENT: &str = r#"APP_NAME=demo
+
+#@ dev_database
+DB_HOST=localhost
+##
+
+#@ staging_database
+DB_HOST=staging.example.com
+##
+
+#@ local_overrides
+DEBUG=true
+##
+"#;
+
+fn block_order(path: &std::path::Path) -> Vec<String> {
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .filter_map(|line| line.strip_prefix("#@ "))
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn move_before_and_after() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = run(
+        &["move", "dev_database", "--before", "local_overrides"],
+        temp_file.path(),
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        block_order(temp_file.path()),
+        ["staging_database", "dev_database", "local_overrides"]
+    );
+
+    let output = run(
+        &["move", "dev_database", "--after", "local_overrides"],
+        temp_file.path(),
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        block_order(temp_file.path()),
+        ["staging_database", "local_overrides", "dev_database"]
+    );
+}
+
+#[test]
+fn move_top_up_and_down() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    run(&["move", "local_overrides", "--top"], temp_file.path());
+    assert_eq!(
+        block_order(temp_file.path()),
+        ["local_overrides", "dev_database", "staging_database"]
+    );
+
+    run(&["move", "staging_database", "--up"], temp_file.path());
+    assert_eq!(
+        block_order(temp_file.path()),
+        ["local_overrides", "staging_database", "dev_database"]
+    );
+
+    run(&["move", "local_overrides", "--down"], temp_file.path());
+    assert_eq!(
+        block_order(temp_file.path()),
+        ["staging_database", "local_overrides", "dev_database"]
+    );
+}
+
+#[test]
+fn move_keeps_surrounding_text() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    run(&["move", "staging_database", "--top"], temp_file.path());
+
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        r#"APP_NAME=demo
+
+#@ staging_database
+DB_HOST=staging.example.com
+##
+
+#@ dev_database
+DB_HOST=localhost
+##
+
+#@ local_overrides
+DEBUG=true
+##
+"#
+    );
+}
+
+#[test]
+fn move_needs_one_position() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = run(&["move", "dev_database"], temp_file.path());
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = run(&["move", "dev_database", "--up", "--top"], temp_file.path());
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn move_unknown_blocks() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = run(
+        &["move", "dev_database", "--before", "nope"],
+        temp_file.path(),
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Block 'nope' was not found"));
+
+    let output = run(&["move", "default", "--top"], temp_file.path());
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        TEST_CONTENT
+    );
+}
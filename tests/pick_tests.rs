@@ -1,6 +1,7 @@
 mod common;
 
 use common::{create_test_env_file, get_binary_path};
+use std::io::Read;
 use std::process::Command;
 
 #[test]
@@ -21,6 +22,7 @@ API_KEY=secret
     let output = Command::new(get_binary_path())
         .arg("pick")
         .arg("database_block")
+        .arg("--file")
         .arg(temp_file.path())
         .output()
         .expect("Failed to execute command");
@@ -54,6 +56,7 @@ VAR=test
     let output = Command::new(get_binary_path())
         .arg("pick")
         .arg("nonexistent_block")
+        .arg("--file")
         .arg(temp_file.path())
         .output()
         .expect("Failed to execute command");
@@ -61,4 +64,178 @@ VAR=test
 
     let _stderr = String::from_utf8_lossy(&output.stderr);
     assert!(_stderr.contains("was not found"));
-}
\ No newline at end of file
+}
+
+#[test]
+fn pick_keeps_everything_else_untouched() {
+    let test_content = "#   Basic config\nAPI_URL=https://api.example.com\n\n\n#@ database_block\nDB_HOST=localhost   # local\n\n##\n\n#@ api_block\nAPI_KEY='secret'\n##\n# trailing note\n";
+
+    let mut temp_file = create_test_env_file(test_content);
+
+    let output = Command::new(get_binary_path())
+        .arg("pick")
+        .arg("database_block")
+        .arg("--file")
+        .arg(temp_file.path())
+        .stdin(std::process::Stdio::null())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let mut buffer = String::new();
+    temp_file.read_to_string(&mut buffer).unwrap();
+    assert_eq!(
+        buffer,
+        "#   Basic config\nAPI_URL=https://api.example.com\n\n\n#@ api_block\nAPI_KEY='secret'\n##\n\n#@ database_block\nDB_HOST=localhost   # local\n\n##\n# trailing note\n"
+    );
+}
+
+#[test]
+fn pick_refuses_unclosed_block() {
+    let test_content =
+        "#@ dev_database\nDB_HOST=example.com\n##\n\n#@ prod_database\nDB_HOST=localhost\n";
+
+    let mut temp_file = create_test_env_file(test_content);
+
+    let output = Command::new(get_binary_path())
+        .arg("pick")
+        .arg("dev_database")
+        .arg("--file")
+        .arg(temp_file.path())
+        .stdin(std::process::Stdio::null())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("error: Block 'prod_database' is never closed\n"));
+    assert!(stderr.contains(":5:1\n"));
+    let mut buffer = String::new();
+    temp_file.read_to_string(&mut buffer).unwrap();
+    assert_eq!(buffer, test_content);
+}
+
+#[test]
+fn pick_keeps_pinned_block_last() {
+    let test_content = r#"#@ dev_database
+DB_HOST=localhost
+##
+#@ local_overrides
+DEBUG=true
+##
+#@ prod_database
+DB_HOST=db.example.com
+##
+"#;
+
+    let temp_file = create_test_env_file(test_content);
+
+    let output = Command::new(get_binary_path())
+        .arg("pick")
+        .arg("dev_database")
+        .arg("--file")
+        .arg(temp_file.path())
+        .arg("--keep-last")
+        .arg("local_overrides")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        "#@ prod_database\nDB_HOST=db.example.com\n##\n#@ dev_database\nDB_HOST=localhost\n##\n#@ local_overrides\nDEBUG=true\n##\n"
+    );
+}
+
+#[test]
+fn pick_several_blocks_in_order() {
+    let test_content =
+        "#@ prod_database\n##\n#@ prod_cache\n##\n#@ dev_database\n##\n#@ prod_email\n##\n";
+
+    let temp_file = create_test_env_file(test_content);
+
+    let output = Command::new(get_binary_path())
+        .arg("pick")
+        .arg("prod_database")
+        .arg("prod_cache")
+        .arg("prod_email")
+        .arg("--file")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        "#@ dev_database\n##\n#@ prod_database\n##\n#@ prod_cache\n##\n#@ prod_email\n##\n"
+    );
+}
+
+#[test]
+fn pick_checks_every_block_first() {
+    let test_content = "#@ prod_database\n##\n#@ dev_database\n##\n";
+
+    let temp_file = create_test_env_file(test_content);
+
+    let output = Command::new(get_binary_path())
+        .arg("pick")
+        .arg("prod_database")
+        .arg("prod_cache")
+        .arg("--file")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Block 'prod_cache' was not found"));
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        test_content
+    );
+}
+
+#[test]
+fn pick_moves_grouped_block_below_its_siblings() {
+    let test_content = "#@ prod_database group=database\n##\n#@ dev_database group=database\n##\n#@ local_overrides\n##\n";
+
+    let temp_file = create_test_env_file(test_content);
+
+    let output = Command::new(get_binary_path())
+        .arg("pick")
+        .arg("prod_database")
+        .arg("--file")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        "#@ dev_database group=database\n##\n#@ prod_database group=database\n##\n#@ local_overrides\n##\n"
+    );
+}
+
+#[test]
+fn pick_takes_every_argument_as_a_block() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("staging"), "#@ a\n##\n#@ b\n##\n").unwrap();
+
+    let pick = |args: &[&str]| {
+        Command::new(get_binary_path())
+            .arg("pick")
+            .args(args)
+            .current_dir(dir.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = pick(&["a", "dev-db", "--file", "staging"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Block 'dev-db' was not found"));
+
+    assert!(pick(&["a", "--file", "staging"]).status.success());
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("staging")).unwrap(),
+        "#@ b\n##\n#@ a\n##\n"
+    );
+}
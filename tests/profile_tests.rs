@@ -0,0 +1,116 @@
+mod common;
+
+use common::run;
+
+const TEST_CONTENT: &str = r#"#@ prod_database
+DB_HOST=db.example.com
+##
+#@ dev_database
+DB_HOST=localhost
+##
+#@ staging_cache
+CACHE_URL=redis://staging
+##
+#@ dev_email
+SMTP_HOST=localhost
+##
+"#;
+
+const CONFIG: &str = r#"staging = ["prod_database", "staging_cache", "dev_email"]
+dev = ["dev_database"]
+"#;
+
+fn project(config: &str) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join(".env"), TEST_CONTENT).unwrap();
+    std::fs::write(dir.path().join(".envmn.toml"), config).unwrap();
+    dir
+}
+
+#[test]
+fn use_applies_the_profile_in_order() {
+    let dir = project(CONFIG);
+
+    let output = run(&["use", "staging"], &dir.path().join(".env"));
+
+    assert!(output.status.success());
+    let blocks = std::fs::read_to_string(dir.path().join(".env"))
+        .unwrap()
+        .lines()
+        .filter_map(|line| line.strip_prefix("#@ "))
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        blocks,
+        [
+            "dev_database",
+            "prod_database",
+            "staging_cache",
+            "dev_email"
+        ]
+    );
+}
+
+#[test]
+fn status_reports_the_matching_profile() {
+    let dir = project(CONFIG);
+
+    let output = run(&["status"], &dir.path().join(".env"));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Profile: none\n"));
+
+    run(&["use", "staging"], &dir.path().join(".env"));
+    let output = run(&["status"], &dir.path().join(".env"));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Profile: staging\n"));
+
+    run(&["use", "dev"], &dir.path().join(".env"));
+    let output = run(&["status"], &dir.path().join(".env"));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Profile: dev\n"));
+}
+
+#[test]
+fn status_prefers_the_longest_matching_profile() {
+    let dir = project(
+        "local = []\nemail = [\"dev_email\"]\nstaging = [\"staging_cache\", \"dev_email\"]\n",
+    );
+
+    run(
+        &["pick", "dev_database", "--file"],
+        &dir.path().join(".env"),
+    );
+    let output = run(&["status"], &dir.path().join(".env"));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Profile: none\n"));
+
+    run(&["use", "staging"], &dir.path().join(".env"));
+    let output = run(&["status"], &dir.path().join(".env"));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Profile: staging\n"));
+}
+
+#[test]
+fn use_checks_the_profile_first() {
+    let dir = project("broken = [\"dev_database\", \"prod_cache\"]\n");
+
+    let output = run(&["use", "broken"], &dir.path().join(".env"));
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Profile 'broken' picks block 'prod_cache', which is not in the file")
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join(".env")).unwrap(),
+        TEST_CONTENT
+    );
+
+    let output = run(&["use", "staging"], &dir.path().join(".env"));
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Profile 'staging' is not defined"));
+}
+
+#[test]
+fn invalid_config_is_reported() {
+    let dir = project("staging = \"prod_database\"\n");
+
+    let output = run(&["status"], &dir.path().join(".env"));
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Profile 'staging' in '"));
+}
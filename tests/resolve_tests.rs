@@ -0,0 +1,188 @@
+mod common;
+
+use common::create_test_env_file;
+
+#[test]
+fn resolve_applies_last_block_wins() {
+    let test_content = r#"APP_NAME=demo
+DB_HOST=localhost
+
+#@ dev_database
+DB_HOST=dev.example.com
+DB_USER=dev
+##
+
+#@ prod_database
+DB_HOST="prod.example.com" # primary
+##
+"#;
+
+    let temp_file = create_test_env_file(test_content);
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("resolve")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "APP_NAME=demo\nDB_HOST=\"prod.example.com\"\nDB_USER=dev\n"
+    );
+}
+
+#[test]
+fn resolve_annotates_blocks() {
+    let temp_file = create_test_env_file("A=1\nB=1\n#@ dev\nB=2\n##\n");
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("resolve")
+        .arg("--annotate")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "A=1 # default\nB=2 # dev\n"
+    );
+}
+
+#[test]
+fn resolve_applies_lines_in_file_order() {
+    let temp_file = create_test_env_file("#@ dev\nA=dev\n##\nA=trailing\n");
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("resolve")
+        .arg("--annotate")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "A=trailing # default\n"
+    );
+}
+
+#[test]
+fn resolve_follows_pick() {
+    let temp_file = create_test_env_file("#@ dev\nA=dev\n##\n#@ prod\nA=prod\n##\n");
+
+    std::process::Command::new(common::get_binary_path())
+        .arg("pick")
+        .arg("dev")
+        .arg("--file")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("resolve")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "A=dev\n");
+}
+
+#[test]
+fn resolve_expands_against_picked_block() {
+    let test_content = r#"DB_USER=app
+DATABASE_URL=postgres://${DB_USER}@${DB_HOST}/${DB_NAME:-main}
+
+#@ prod_database
+DB_HOST=prod.example.com
+##
+
+#@ dev_database
+DB_HOST=localhost
+##
+"#;
+
+    let temp_file = create_test_env_file(test_content);
+
+    let resolve = || {
+        std::process::Command::new(common::get_binary_path())
+            .arg("resolve")
+            .arg("--expand")
+            .arg(temp_file.path())
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = resolve();
+    assert!(output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stdout)
+            .contains("DATABASE_URL=postgres://app@localhost/main\n")
+    );
+
+    std::process::Command::new(common::get_binary_path())
+        .arg("pick")
+        .arg("prod_database")
+        .arg("--file")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        String::from_utf8_lossy(&resolve().stdout)
+            .contains("DATABASE_URL=postgres://app@prod.example.com/main\n")
+    );
+}
+
+#[test]
+fn resolve_expand_output_reads_back_the_same() {
+    let resolve = |path: &std::path::Path| {
+        std::process::Command::new(common::get_binary_path())
+            .arg("resolve")
+            .arg("--expand")
+            .arg(path)
+            .output()
+            .expect("Failed to execute command")
+    };
+    let temp_file = create_test_env_file("X=1\nA=\"\\$X\"\nB=\"\\\\$X\"\n");
+
+    let output = resolve(temp_file.path());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout, "X=1\nA='$X'\nB='\\1'\n");
+    let again = create_test_env_file(&stdout);
+    assert_eq!(
+        String::from_utf8_lossy(&resolve(again.path()).stdout),
+        stdout
+    );
+}
+
+#[test]
+fn resolve_without_expand_keeps_references() {
+    let temp_file = create_test_env_file("A=1\nB=${A}\n");
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("resolve")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "A=1\nB=${A}\n");
+}
+
+#[test]
+fn resolve_expand_reports_required_variable() {
+    let temp_file = create_test_env_file("URL=${ENVMN_TEST_UNSET_HOST:?set the host}\n");
+
+    let output = std::process::Command::new(common::get_binary_path())
+        .arg("resolve")
+        .arg("--expand")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Variable 'ENVMN_TEST_UNSET_HOST' is not set: set the host")
+    );
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use common::{create_test_env_file, get_binary_path, run};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const TEST_CONTENT: &str = r#"# App settings
+APP_NAME=demo # shown in the title
+
+#@ prod_database
+DB_HOST=localhost
+DB_PASSWORD="old"
+##
+"#;
+
+#[test]
+fn set_updates_in_place() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = run(
+        &[
+            "set",
+            "DB_HOST",
+            "db.example.com",
+            "--block",
+            "prod_database",
+        ],
+        temp_file.path(),
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        TEST_CONTENT.replace("DB_HOST=localhost", "DB_HOST=db.example.com")
+    );
+}
+
+#[test]
+fn set_adds_quoted_variable() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    run(&["set", "GREETING", "hello $USER"], temp_file.path());
+    run(
+        &["set", "DB_PORT", "5432", "--block", "prod_database"],
+        temp_file.path(),
+    );
+
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        "# App settings\nAPP_NAME=demo # shown in the title\nGREETING='hello $USER'\n\n#@ prod_database\nDB_HOST=localhost\nDB_PASSWORD=\"old\"\nDB_PORT=5432\n##\n"
+    );
+}
+
+#[test]
+fn set_reads_value_from_stdin() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let mut child = Command::new(get_binary_path())
+        .args(["set", "DB_PASSWORD", "-", "--block", "prod_database"])
+        .arg(temp_file.path())
+        .stdin(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"s3cr\"et\n")
+        .unwrap();
+    assert!(child.wait().unwrap().success());
+
+    let output = run(
+        &["get", "DB_PASSWORD", "--block", "prod_database"],
+        temp_file.path(),
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "s3cr\"et\n");
+    assert!(
+        std::fs::read_to_string(temp_file.path())
+            .unwrap()
+            .contains("DB_PASSWORD=\"s3cr\\\"et\"\n")
+    );
+}
+
+#[test]
+fn set_into_missing_block() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = run(&["set", "A", "1", "--block", "nope"], temp_file.path());
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Block 'nope' was not found"));
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        TEST_CONTENT
+    );
+}
+
+#[test]
+fn get_prints_winning_value() {
+    let temp_file = create_test_env_file("DB_HOST=localhost\n#@ prod\nDB_HOST='db host'\n##\n");
+
+    let output = run(&["get", "DB_HOST"], temp_file.path());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "db host\n");
+
+    let output = run(&["get", "DB_HOST", "--block", "default"], temp_file.path());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "localhost\n");
+
+    let output = run(&["get", "MISSING"], temp_file.path());
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn unset_removes_variable() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = run(
+        &["unset", "DB_PASSWORD", "--block", "prod_database"],
+        temp_file.path(),
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        TEST_CONTENT.replace("DB_PASSWORD=\"old\"\n", "")
+    );
+
+    let output = run(&["unset", "DB_PASSWORD"], temp_file.path());
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Variable 'DB_PASSWORD' was not found in block 'default'")
+    );
+}
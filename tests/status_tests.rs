@@ -0,0 +1,85 @@
+mod common;
+
+use common::{create_test_env_file, get_binary_path};
+use std::process::Command;
+
+#[test]
+fn status_shows_groups_and_variables() {
+    let temp_file = create_test_env_file(
+        r#"APP_NAME=demo
+#@ prod_database group=database
+DB_HOST=db.example.com
+##
+#@ dev_database group=database
+DB_HOST=localhost
+##
+#@ local_overrides
+DEBUG=true
+##
+"#,
+    );
+
+    let output = Command::new(get_binary_path())
+        .arg("status")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Profile: none\n\nGroups:\n  database  dev_database\n\nVariables:\n  APP_NAME  default\n  DB_HOST   dev_database\n  DEBUG     local_overrides\n"
+    );
+}
+
+#[test]
+fn status_without_groups() {
+    let temp_file = create_test_env_file("A=1\n");
+
+    let output = Command::new(get_binary_path())
+        .arg("status")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Profile: none\n\nVariables:\n  A  default\n"
+    );
+}
+
+#[test]
+fn status_shows_siblings_by_name() {
+    let temp_file = create_test_env_file(
+        r#"APP_NAME=demo
+#@ prod_database
+DB_HOST=db.example.com
+##
+#@ dev_database
+DB_HOST=localhost
+##
+#@ prod_cache
+CACHE_URL=redis://prod
+##
+#@ email_dev
+SMTP_HOST=localhost
+##
+#@ email_prod
+SMTP_HOST=smtp.example.com
+##
+"#,
+    );
+
+    let output = Command::new(get_binary_path())
+        .arg("status")
+        .arg(temp_file.path())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Profile: none\n\nGroups:\n  *_database  dev_database\n  email_*     email_prod\n\nVariables:\n  APP_NAME   default\n  DB_HOST    dev_database\n  CACHE_URL  prod_cache\n  SMTP_HOST  email_prod\n"
+    );
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use common::{create_test_env_file, run};
+
+const TEST_CONTENT: &str = r#"APP_NAME=demo
+
+#@ prod_database
+DB_HOST=db.example.com
+##
+
+#@ dev_database
+DB_HOST=localhost
+##
+
+#@ email_prod
+SMTP_HOST=smtp.example.com
+##
+
+#@ email_dev
+SMTP_HOST=localhost
+##
+"#;
+
+#[test]
+fn switch_picks_every_block_of_the_environment() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = run(&["switch", "prod"], temp_file.path());
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "prod_database  replaces dev_database\nemail_prod     replaces email_dev\n"
+    );
+    let output = run(&["resolve"], temp_file.path());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "APP_NAME=demo\nDB_HOST=db.example.com\nSMTP_HOST=smtp.example.com\n"
+    );
+}
+
+#[test]
+fn switch_back_and_forth() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    run(&["switch", "prod"], temp_file.path());
+    let output = run(&["switch", "prod"], temp_file.path());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "prod_database  already active\nemail_prod     already active\n"
+    );
+
+    run(&["switch", "dev"], temp_file.path());
+    let output = run(&["resolve"], temp_file.path());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "APP_NAME=demo\nDB_HOST=localhost\nSMTP_HOST=localhost\n"
+    );
+}
+
+#[test]
+fn switch_to_unknown_environment() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = run(&["switch", "staging"], temp_file.path());
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("No block belongs to environment 'staging'")
+    );
+    assert_eq!(
+        std::fs::read_to_string(temp_file.path()).unwrap(),
+        TEST_CONTENT
+    );
+}
+
+#[test]
+fn switch_leaves_blocks_without_siblings() {
+    let content = format!("#@ prod_metrics\nMETRICS=on\n##\n{TEST_CONTENT}");
+    let temp_file = create_test_env_file(&content);
+
+    let output = run(&["switch", "prod"], temp_file.path());
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "prod_database  replaces dev_database\nemail_prod     replaces email_dev\n"
+    );
+    assert!(
+        std::fs::read_to_string(temp_file.path())
+            .unwrap()
+            .starts_with("#@ prod_metrics\n")
+    );
+}
+
+#[test]
+fn switch_and_status_agree_on_siblings() {
+    let content = format!("#@ prod_db\nH=1\n##\n#@ dev_db\nZ=1\n##\n{TEST_CONTENT}");
+    let temp_file = create_test_env_file(&content);
+
+    let output = run(&["switch", "prod"], temp_file.path());
+
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "prod_database  replaces dev_database\nemail_prod     replaces email_dev\n"
+    );
+    let output = run(&["status"], temp_file.path());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Groups:\n  *_database  prod_database\n  email_*     email_prod\n\n"));
+    assert!(!stdout.contains("*_db "));
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use common::{create_test_env_file, run};
+use std::fs;
+
+const TEST_CONTENT: &str = r#"APP_NAME=demo
+
+#@ prod_database
+DB_HOST=db.example.com
+##
+
+#@ dev_database
+# local only
+# ROTATE=yearly
+DB_HOST=localhost
+DB_CERT="-----BEGIN-----
+abc
+-----END-----"
+##
+"#;
+
+const DISABLED_CONTENT: &str = r#"APP_NAME=demo
+
+#@ prod_database
+DB_HOST=db.example.com
+##
+
+#@! dev_database
+# local only
+# ROTATE=yearly
+#! DB_HOST=localhost
+#! DB_CERT="-----BEGIN-----
+#! abc
+#! -----END-----"
+##
+"#;
+
+#[test]
+fn disable_comments_out_the_block() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = run(&["disable", "dev_database"], temp_file.path());
+
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_to_string(temp_file.path()).unwrap(),
+        DISABLED_CONTENT
+    );
+}
+
+#[test]
+fn enable_restores_the_block() {
+    let temp_file = create_test_env_file(DISABLED_CONTENT);
+
+    let output = run(&["enable", "dev_database"], temp_file.path());
+
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(temp_file.path()).unwrap(), TEST_CONTENT);
+}
+
+#[test]
+fn toggling_twice_changes_nothing() {
+    let temp_file = create_test_env_file(DISABLED_CONTENT);
+
+    assert!(
+        run(&["disable", "dev_database"], temp_file.path())
+            .status
+            .success()
+    );
+    assert!(
+        run(&["enable", "prod_database"], temp_file.path())
+            .status
+            .success()
+    );
+
+    assert_eq!(
+        fs::read_to_string(temp_file.path()).unwrap(),
+        DISABLED_CONTENT
+    );
+}
+
+#[test]
+fn resolve_and_export_skip_disabled_blocks() {
+    let temp_file = create_test_env_file(DISABLED_CONTENT);
+
+    let output = run(&["resolve"], temp_file.path());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "APP_NAME=demo\nDB_HOST=db.example.com\n"
+    );
+    let output = run(&["export", "--shell", "bash"], temp_file.path());
+    assert!(output.status.success());
+    let exported = String::from_utf8_lossy(&output.stdout);
+    assert!(exported.contains("db.example.com"));
+    assert!(!exported.contains("localhost"));
+    assert!(!exported.contains("DB_CERT"));
+}
+
+#[test]
+fn live_variables_in_disabled_blocks_are_kept() {
+    let content = "#@ prod\nA=prod\n##\n#@! dev\nA=dev\n##\n";
+    let temp_file = create_test_env_file(content);
+
+    let output = run(&["resolve"], temp_file.path());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "A=dev\n");
+    let output = run(&["lint"], temp_file.path());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Variable 'A' is not commented out"));
+
+    assert!(
+        run(&["pick", "prod", "--file"], temp_file.path())
+            .status
+            .success()
+    );
+    assert_eq!(
+        fs::read_to_string(temp_file.path()).unwrap(),
+        "#@! dev\nA=dev\n##\n#@ prod\nA=prod\n##\n"
+    );
+    assert!(run(&["disable", "dev"], temp_file.path()).status.success());
+    assert_eq!(
+        fs::read_to_string(temp_file.path()).unwrap(),
+        "#@! dev\n#! A=dev\n##\n#@ prod\nA=prod\n##\n"
+    );
+}
+
+#[test]
+fn list_shows_disabled_blocks() {
+    let temp_file = create_test_env_file(DISABLED_CONTENT);
+
+    let output = run(&["list"], temp_file.path());
+
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Blocks (3):\n- default\n- prod_database\n- dev_database (disabled)\n"
+    );
+}
+
+#[test]
+fn default_block_cannot_be_disabled() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = run(&["disable", "default"], temp_file.path());
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("default block is always enabled"));
+    assert_eq!(fs::read_to_string(temp_file.path()).unwrap(), TEST_CONTENT);
+}
+
+#[test]
+fn unknown_block_is_an_error() {
+    let temp_file = create_test_env_file(TEST_CONTENT);
+
+    let output = run(&["enable", "staging"], temp_file.path());
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Block 'staging' was not found"));
+}